@@ -0,0 +1,12 @@
+//! GHASH and POLYVAL over an arbitrary H and message against RustCrypto; see
+//! `aes_proof_gfmul_prototype::differential`.
+#![no_main]
+
+use aes_proof_gfmul_prototype::differential::check;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Err(e) = check(data) {
+        panic!("{e:#}");
+    }
+});
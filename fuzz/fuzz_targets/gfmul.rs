@@ -0,0 +1,12 @@
+//! Every gfmul backend on arbitrary operands against RustCrypto; see
+//! `aes_proof_gfmul_prototype::differential`.
+#![no_main]
+
+use aes_proof_gfmul_prototype::differential::check_gfmul;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Err(e) = check_gfmul(data) {
+        panic!("{e:#}");
+    }
+});
@@ -0,0 +1,126 @@
+//! A software AES block cipher (FIPS 197), encryption only, for use inside GCM.
+//!
+//! Written for readability over speed: every round is the textbook SubBytes, ShiftRows,
+//! MixColumns and AddRoundKey over a column-major 16-byte state, so the steps can be mirrored one
+//! for one in circuits.
+//!
+//! Not constant-time: SubBytes indexes the S-box with key- and data-dependent bytes, and `xtime`
+//! branches on the top bit, so cache and branch timing leak the key. This cipher is a reference
+//! for testing, not for protecting secrets.
+
+use std::fmt;
+
+use anyhow::{bail, Result};
+
+#[cfg(test)] mod tests;
+
+/// The AES S-box.
+const SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+/// Round constants for the key schedule; `RCON[i]` = x^i in GF(2^8).
+const RCON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+/// An expanded AES-128, AES-192 or AES-256 key. `Debug` shows only the number of rounds.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Aes {
+    /// Nr + 1 round keys
+    round_keys: Vec<[u8; 16]>,
+}
+
+impl fmt::Debug for Aes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Aes").field("rounds", &(self.round_keys.len() - 1)).finish_non_exhaustive()
+    }
+}
+
+impl Aes {
+    /// Expand a 16, 24 or 32 byte key.
+    pub fn new(key: &[u8]) -> Result<Self> {
+        let nk = match key.len() {
+            16 | 24 | 32 => key.len() / 4,
+            n => bail!("AES key must be 16, 24 or 32 bytes, got {n}"),
+        };
+        let nr = nk + 6;
+
+        let mut words: Vec<[u8; 4]> = key.chunks(4).map(|w| w.try_into().unwrap()).collect();
+        for i in nk..4 * (nr + 1) {
+            let mut temp = words[i - 1];
+            if i % nk == 0 {
+                temp.rotate_left(1);
+                temp = temp.map(sub_byte);
+                temp[0] ^= RCON[i / nk - 1];
+            } else if nk > 6 && i % nk == 4 {
+                temp = temp.map(sub_byte);
+            }
+            let prev = words[i - nk];
+            words.push(core::array::from_fn(|j| prev[j] ^ temp[j]));
+        }
+
+        let round_keys =
+            words.chunks(4).map(|w| core::array::from_fn(|i| w[i / 4][i % 4])).collect();
+        Ok(Self { round_keys })
+    }
+
+    /// The number of rounds Nr: 10, 12 or 14.
+    pub fn rounds(&self) -> usize { self.round_keys.len() - 1 }
+
+    /// Encrypt a single block.
+    pub fn encrypt_block(&self, block: [u8; 16]) -> [u8; 16] {
+        let nr = self.rounds();
+        let mut state = add_round_key(block, self.round_keys[0]);
+        for round_key in &self.round_keys[1..nr] {
+            state = add_round_key(mix_columns(shift_rows(sub_bytes(state))), *round_key);
+        }
+        add_round_key(shift_rows(sub_bytes(state)), self.round_keys[nr])
+    }
+}
+
+fn sub_byte(b: u8) -> u8 { SBOX[b as usize] }
+
+fn sub_bytes(state: [u8; 16]) -> [u8; 16] { state.map(sub_byte) }
+
+/// row r is rotated left by r; byte (r, c) lives at index r + 4c
+fn shift_rows(state: [u8; 16]) -> [u8; 16] {
+    core::array::from_fn(|i| {
+        let (r, c) = (i % 4, i / 4);
+        state[r + 4 * ((c + r) % 4)]
+    })
+}
+
+/// multiply by x in GF(2^8) modulo x^8 + x^4 + x^3 + x + 1
+fn xtime(b: u8) -> u8 { (b << 1) ^ if b & 0x80 != 0 { 0x1b } else { 0 } }
+
+/// each column is multiplied by 3x^3 + x^2 + x + 2 modulo x^4 + 1
+fn mix_columns(state: [u8; 16]) -> [u8; 16] {
+    let mut out = [0; 16];
+    for c in 0..4 {
+        let col = &state[4 * c..4 * c + 4];
+        for r in 0..4 {
+            let (a0, a1, a2, a3) = (col[r], col[(r + 1) % 4], col[(r + 2) % 4], col[(r + 3) % 4]);
+            // 2·a0 + 3·a1 + a2 + a3
+            out[4 * c + r] = xtime(a0) ^ xtime(a1) ^ a1 ^ a2 ^ a3;
+        }
+    }
+    out
+}
+
+fn add_round_key(state: [u8; 16], round_key: [u8; 16]) -> [u8; 16] {
+    core::array::from_fn(|i| state[i] ^ round_key[i])
+}
@@ -0,0 +1,77 @@
+use aes_gcm::aes::{
+    cipher::{BlockEncrypt, KeyInit},
+    Aes128, Aes192, Aes256,
+};
+use hex_literal::hex;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+
+#[test]
+fn test_invalid_key_length() {
+    assert!(Aes::new(&[0; 15]).is_err());
+    assert!(Aes::new(&[0; 20]).is_err());
+    assert!(Aes::new(&[]).is_err());
+}
+
+#[test]
+fn test_rounds() {
+    assert_eq!(Aes::new(&[0; 16]).unwrap().rounds(), 10);
+    assert_eq!(Aes::new(&[0; 24]).unwrap().rounds(), 12);
+    assert_eq!(Aes::new(&[0; 32]).unwrap().rounds(), 14);
+}
+
+#[test]
+fn test_steps() {
+    assert_eq!(sub_byte(0x00), 0x63);
+    assert_eq!(sub_byte(0x53), 0xed);
+    assert_eq!(xtime(0x57), 0xae);
+    assert_eq!(xtime(0xae), 0x47);
+    // FIPS 197 appendix B, round 1
+    let state = hex!("d42711aee0bf98f1b8b45de51e415230");
+    assert_eq!(shift_rows(state), hex!("d4bf5d30e0b452aeb84111f11e2798e5"));
+    assert_eq!(mix_columns(shift_rows(state)), hex!("046681e5e0cb199a48f8d37a2806264c"));
+}
+
+#[test]
+fn test_key_expansion() {
+    // FIPS 197 appendix A.1
+    let aes = Aes::new(&hex!("2b7e151628aed2a6abf7158809cf4f3c")).unwrap();
+    assert_eq!(aes.round_keys[1], hex!("a0fafe1788542cb123a339392a6c7605"));
+    assert_eq!(aes.round_keys[10], hex!("d014f9a8c9ee2589e13f0cc8b6630ca6"));
+}
+
+#[test]
+fn test_fips197_appendix_c() {
+    let pt = hex!("00112233445566778899aabbccddeeff");
+    let key = hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+    assert_eq!(
+        Aes::new(&key[..16]).unwrap().encrypt_block(pt),
+        hex!("69c4e0d86a7b0430d8cdb78070b4c55a")
+    );
+    assert_eq!(
+        Aes::new(&key[..24]).unwrap().encrypt_block(pt),
+        hex!("dda97ca4864cdfe06eaf70a0ec0d7191")
+    );
+    assert_eq!(Aes::new(&key).unwrap().encrypt_block(pt), hex!("8ea2b7ca516745bfeafc49904b496089"));
+}
+
+#[test]
+fn test_matches_rustcrypto() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..100 {
+        let (key, pt): ([u8; 32], [u8; 16]) = (rng.gen(), rng.gen());
+
+        let mut block = pt.into();
+        Aes128::new(key[..16].into()).encrypt_block(&mut block);
+        assert_eq!(Aes::new(&key[..16]).unwrap().encrypt_block(pt), block.as_slice());
+
+        let mut block = pt.into();
+        Aes192::new(key[..24].into()).encrypt_block(&mut block);
+        assert_eq!(Aes::new(&key[..24]).unwrap().encrypt_block(pt), block.as_slice());
+
+        let mut block = pt.into();
+        Aes256::new(&key.into()).encrypt_block(&mut block);
+        assert_eq!(Aes::new(&key).unwrap().encrypt_block(pt), block.as_slice());
+    }
+}
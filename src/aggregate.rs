@@ -0,0 +1,65 @@
+//! GHASH with aggregated reduction over precomputed powers of the hash key.
+//!
+//! Unrolling n steps of the GHASH recurrence Y_i = (Y_{i-1} + X_i)·H gives
+//!
+//! Y_{i+n} = (Y_i + X_{i+1})·H^n + X_{i+2}·H^{n-1} + ... + X_{i+n}·H
+//!
+//! so with H^1..H^n precomputed, a group of n blocks costs n unreduced carryless products that are
+//! xored together, and a single reduction.
+
+use crate::{
+    parse_array_as_uint, parse_u128_as_array,
+    pclmul::{clmul128, pclmul_gfmul},
+    reduce_product,
+};
+
+#[cfg(test)] mod tests;
+
+/// A hash key H with precomputed powers H^1..H^n, for processing n blocks per reduction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GhashPowers {
+    /// `powers[i]` = H^(i+1)
+    powers: Vec<u128>,
+}
+
+impl GhashPowers {
+    /// Precompute H^1..H^n for the GHASH-convention hash key `h`.
+    ///
+    /// Panics if `n` is zero.
+    pub fn new(h: [u8; 16], n: usize) -> Self {
+        assert!(n > 0, "aggregation width must be at least 1");
+        let powers = (1..n)
+            .scan(h, |h_i, _| {
+                *h_i = pclmul_gfmul(*h_i, h);
+                Some(*h_i)
+            })
+            .map(parse_array_as_uint);
+        let powers = core::iter::once(parse_array_as_uint(h)).chain(powers).collect();
+
+        Self { powers }
+    }
+
+    /// The number of blocks processed per reduction.
+    pub fn width(&self) -> usize { self.powers.len() }
+
+    /// H^i in the GHASH convention, for 1 <= i <= [`GhashPowers::width`].
+    pub fn power(&self, i: usize) -> [u8; 16] { parse_u128_as_array(self.powers[i - 1]) }
+
+    /// [`crate::ghash`] with one reduction per group of [`GhashPowers::width`] blocks.
+    ///
+    /// A trailing group of m < n blocks uses H^m..H^1.
+    pub fn ghash(&self, blocks: &[[u8; 16]]) -> [u8; 16] {
+        let y = blocks.chunks(self.width()).fold(0, |y, group| {
+            let m = group.len();
+            let (upper, lower) = group.iter().enumerate().fold((0, 0), |(upper, lower), (i, x)| {
+                // the running hash is absorbed into the first block of the group
+                let x = parse_array_as_uint(*x) ^ if i == 0 { y } else { 0 };
+                let (hi, lo) = clmul128(x, self.powers[m - 1 - i]);
+                (upper ^ hi, lower ^ lo)
+            });
+            reduce_product(upper, lower)
+        });
+
+        parse_u128_as_array(y)
+    }
+}
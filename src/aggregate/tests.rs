@@ -0,0 +1,54 @@
+use ghash::{
+    universal_hash::{KeyInit, UniversalHash},
+    GHash,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+use crate::{gfmul, ghash, tests::*};
+
+#[test]
+fn test_powers() {
+    let powers = GhashPowers::new(H, 8);
+    assert_eq!(powers.width(), 8);
+    assert_eq!(powers.power(1), H);
+    assert_eq!(powers.power(2), gfmul(H, H));
+    assert_eq!(powers.power(3), gfmul(gfmul(H, H), H));
+    for i in 2..=8 {
+        assert_eq!(powers.power(i), gfmul(powers.power(i - 1), H));
+    }
+    // x^0 is fixed by every power
+    assert_eq!(GhashPowers::new(LONE, 4).power(4), LONE);
+}
+
+#[test]
+#[should_panic]
+fn test_zero_width() { GhashPowers::new(H, 0); }
+
+#[test]
+fn test_ghash_vectors() {
+    for n in [1, 2, 4, 8] {
+        assert_eq!(GhashPowers::new(H, n).ghash(&[]), ghash(H, &[]));
+        assert_eq!(GhashPowers::new(H, n).ghash(&[X_1]), ghash(H, &[X_1]));
+        assert_eq!(GhashPowers::new(H, n).ghash(&[X_1, X_2]), ghash(H, &[X_1, X_2]));
+        assert_eq!(GhashPowers::new(LC, n).ghash(&[RTHREE0]), ghash(LC, &[RTHREE0]));
+        assert_eq!(GhashPowers::new(RONE, n).ghash(&[RONE, RTWO]), ghash(RONE, &[RONE, RTWO]));
+    }
+}
+
+#[test]
+fn test_ghash_random_lengths() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for n in [1, 2, 3, 4, 8] {
+        for len in 0..20 {
+            let h: [u8; 16] = rng.gen();
+            let blocks: Vec<[u8; 16]> = (0..len).map(|_| rng.gen()).collect();
+            let powers = GhashPowers::new(h, n);
+            assert_eq!(powers.ghash(&blocks), ghash(h, &blocks), "n={n}, len={len}");
+
+            let mut ghash_rc = GHash::new(&h.into());
+            blocks.iter().for_each(|b| ghash_rc.update(&[(*b).into()]));
+            assert_eq!(ghash_rc.finalize().as_slice(), powers.ghash(&blocks));
+        }
+    }
+}
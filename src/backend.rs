@@ -0,0 +1,50 @@
+//! Selection between the gfmul implementations in this crate.
+
+use crate::{
+    ct::ct_gfmul, gfmul, karatsuba::karatsuba_gfmul, pclmul::pclmul_gfmul, spread::spread_gfmul,
+};
+
+/// A multiplication strategy over $\text{GF}(2^{128})$; every variant computes the same product.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// The bit-serial shift-and-xor [`gfmul`].
+    BitSerial,
+    /// Single-level Karatsuba over 64-bit halves, see [`karatsuba_gfmul`].
+    Karatsuba,
+    /// `PCLMULQDQ` when the CPU supports it, with a portable fallback; see [`pclmul_gfmul`].
+    Pclmulqdq,
+    /// Branch-free shift-and-xor with masks, see [`ct_gfmul`].
+    ConstantTime,
+    /// Integer multiplication of bit-spread operands, see [`spread_gfmul`].
+    Spread,
+}
+
+impl Backend {
+    /// Every backend, for differential testing.
+    pub const ALL: [Backend; 5] = [
+        Backend::BitSerial,
+        Backend::Karatsuba,
+        Backend::Pclmulqdq,
+        Backend::ConstantTime,
+        Backend::Spread,
+    ];
+
+    /// Multiply two GHASH-convention field elements with this backend.
+    pub fn gfmul(self, a: [u8; 16], b: [u8; 16]) -> [u8; 16] {
+        match self {
+            Backend::BitSerial => gfmul(a, b),
+            Backend::Karatsuba => karatsuba_gfmul(a, b),
+            Backend::Pclmulqdq => pclmul_gfmul(a, b),
+            Backend::ConstantTime => ct_gfmul(a, b),
+            Backend::Spread => spread_gfmul(a, b),
+        }
+    }
+
+    /// [`crate::ghash`], multiplying by the hash key with this backend.
+    pub fn ghash(self, hashkey: [u8; 16], blocks: &[[u8; 16]]) -> [u8; 16] {
+        blocks.iter().fold([0u8; 16], |mut x, block| {
+            (0..16).for_each(|i| x[i] ^= block[i]);
+            self.gfmul(x, hashkey)
+        })
+    }
+}
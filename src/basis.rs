@@ -0,0 +1,78 @@
+//! Exhaustive equivalence of gfmul implementations on the basis pairs $x^i \cdot x^j$.
+//!
+//! Multiplication is bilinear over GF(2): a·b is the xor of $x^i \cdot x^j$ over the set bits
+//! $i$ of a and $j$ of b. So two bilinear implementations that agree on all 128×128 basis pairs
+//! agree on every input, and [`check_basis_equivalence`] is a proof of equality rather than a
+//! sample. Bilinearity itself is the candidate's to uphold; an implementation that is not
+//! linear, such as `_sadmode_gfmul`, can agree on the basis and still be wrong.
+
+use core::fmt;
+
+use anyhow::bail;
+
+use crate::parse_u128_as_array;
+
+#[cfg(test)] mod tests;
+
+/// The block $x^i$.
+pub fn basis(i: usize) -> [u8; 16] {
+    assert!(i < 128, "x^{i} is not a basis element");
+    parse_u128_as_array(1 << i)
+}
+
+/// A basis pair on which the candidate and the reference differ.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BasisMismatch {
+    /// the exponent of the left operand
+    pub i:         usize,
+    /// the exponent of the right operand
+    pub j:         usize,
+    pub expected:  [u8; 16],
+    pub candidate: [u8; 16],
+}
+
+impl fmt::Display for BasisMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "x^{} * x^{}: expected {}, got {}",
+            self.i,
+            self.j,
+            hex::encode(self.expected),
+            hex::encode(self.candidate)
+        )
+    }
+}
+
+/// Every basis pair, in order of `(i, j)`, on which `candidate` differs from `reference`.
+pub fn basis_mismatches(
+    candidate: impl Fn([u8; 16], [u8; 16]) -> [u8; 16],
+    reference: impl Fn([u8; 16], [u8; 16]) -> [u8; 16],
+) -> Vec<BasisMismatch> {
+    let mut mismatches = vec![];
+    for i in 0..128 {
+        for j in 0..128 {
+            let (a, b) = (basis(i), basis(j));
+            let (expected, candidate) = (reference(a, b), candidate(a, b));
+            if expected != candidate {
+                mismatches.push(BasisMismatch { i, j, expected, candidate });
+            }
+        }
+    }
+    mismatches
+}
+
+/// Check all 16384 basis products of `candidate` against `reference`; the error lists up to the
+/// first 16 mismatching pairs and counts the rest.
+pub fn check_basis_equivalence(
+    candidate: impl Fn([u8; 16], [u8; 16]) -> [u8; 16],
+    reference: impl Fn([u8; 16], [u8; 16]) -> [u8; 16],
+) -> anyhow::Result<()> {
+    let mismatches = basis_mismatches(candidate, reference);
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+
+    let listed: Vec<String> = mismatches.iter().take(16).map(|m| m.to_string()).collect();
+    bail!("{} of 16384 basis products differ:\n{}", mismatches.len(), listed.join("\n"))
+}
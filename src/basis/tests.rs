@@ -0,0 +1,50 @@
+use super::*;
+use crate::{_sadmode_gfmul, gfmul, tests::*, Backend, Gf128};
+
+#[test]
+fn test_basis() {
+    assert_eq!(basis(0), LONE);
+    assert_eq!(basis(1), LTWO);
+    assert_eq!(basis(127), RONE);
+    assert_eq!(basis(126), RTWO);
+}
+
+#[test]
+fn test_backends_equal_on_basis() {
+    for backend in Backend::ALL {
+        check_basis_equivalence(|a, b| backend.gfmul(a, b), gfmul)
+            .unwrap_or_else(|e| panic!("{backend:?}: {e}"));
+    }
+    check_basis_equivalence(
+        |a, b| (Gf128::from_ghash_bytes(a) * Gf128::from_ghash_bytes(b)).to_ghash_bytes(),
+        gfmul,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_basis_mismatches() {
+    // forget the x^7 term of the reduction: only products of degree 128 and up, which need
+    // reducing, are affected
+    let broken = |a, b| {
+        let (a, b) = (Gf128::from_ghash_bytes(a).to_uint(), Gf128::from_ghash_bytes(b).to_uint());
+        let (upper, lower) = crate::clmul128(a, b);
+        let reduce = |u: u128| u ^ (u << 1) ^ (u << 2);
+        let spill = (upper >> 127) ^ (upper >> 126);
+        Gf128::from_uint(lower ^ reduce(upper ^ spill)).to_ghash_bytes()
+    };
+    let mismatches = basis_mismatches(broken, gfmul);
+    assert!(!mismatches.is_empty());
+    assert!(mismatches.iter().all(|m| m.i + m.j >= 128));
+    assert!(mismatches.iter().any(|m| (m.i, m.j) == (127, 1)));
+    assert_eq!(mismatches[0].expected, gfmul(basis(mismatches[0].i), basis(mismatches[0].j)));
+
+    let message = check_basis_equivalence(broken, gfmul).unwrap_err().to_string();
+    assert!(message.starts_with(&format!("{} of 16384", mismatches.len())));
+    assert!(message.contains(&format!("x^{} * x^{}", mismatches[0].i, mismatches[0].j)));
+
+    // single-bit integer products carry nothing, so the integer-multiplication attempt agrees on
+    // the basis: it is not bilinear, and the check proves nothing for it
+    assert!(basis_mismatches(_sadmode_gfmul, gfmul).is_empty());
+    assert_ne!(_sadmode_gfmul(X_1, H), gfmul(X_1, H));
+}
@@ -0,0 +1,402 @@
+//! Bristol Fashion boolean circuits for gfmul and GHASH, for MPC and garbled-circuit frameworks.
+//!
+//! A circuit file reads:
+//!
+//! ```text
+//! <gates> <wires>
+//! <number of inputs> <bits of input 0> <bits of input 1> ...
+//! <number of outputs> <bits of output 0> ...
+//!
+//! 2 1 <a> <b> <out> XOR
+//! 2 1 <a> <b> <out> AND
+//! 1 1 <a> <out> INV
+//! 1 1 <a> <out> EQW
+//! 1 1 <0|1> <out> EQ
+//! ```
+//!
+//! Input wires come first, in order, and output wires are the last wires of the circuit. Within
+//! a 128-bit value, wire `k` carries the coefficient of $x^k$: the bit order of
+//! `parse_array_as_bits`, which is the most significant bit of byte 0 first. [`block_wires`] and
+//! [`wires_block`] convert.
+//!
+//! Circuits are recorded by running the generic strategies of [`crate::cost`] over [`WireBit`]s.
+
+use core::{
+    cell::RefCell,
+    fmt,
+    ops::{BitAnd, BitXor, Not},
+};
+
+use anyhow::{anyhow, bail, ensure, Context};
+
+use crate::{
+    cost::{bits_block, block_bits, clmul_recursive_karatsuba, reduce, Bit, CostStrategy},
+    parse_array_as_bits,
+};
+
+#[cfg(test)] mod tests;
+
+/// A gate over wire indices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Gate {
+    Xor(usize, usize, usize),
+    And(usize, usize, usize),
+    Inv(usize, usize),
+    /// copy a wire
+    Eqw(usize, usize),
+    /// assign a constant
+    Eq(bool, usize),
+}
+
+impl Gate {
+    fn output(self) -> usize {
+        match self {
+            Gate::Xor(_, _, out)
+            | Gate::And(_, _, out)
+            | Gate::Inv(_, out)
+            | Gate::Eqw(_, out)
+            | Gate::Eq(_, out) => out,
+        }
+    }
+
+    /// Every wire the gate reads or writes.
+    fn wires(self) -> Vec<usize> {
+        match self {
+            Gate::Xor(a, b, out) | Gate::And(a, b, out) => vec![a, b, out],
+            Gate::Inv(a, out) | Gate::Eqw(a, out) => vec![a, out],
+            Gate::Eq(_, out) => vec![out],
+        }
+    }
+}
+
+impl fmt::Display for Gate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Gate::Xor(a, b, out) => write!(f, "2 1 {a} {b} {out} XOR"),
+            Gate::And(a, b, out) => write!(f, "2 1 {a} {b} {out} AND"),
+            Gate::Inv(a, out) => write!(f, "1 1 {a} {out} INV"),
+            Gate::Eqw(a, out) => write!(f, "1 1 {a} {out} EQW"),
+            Gate::Eq(b, out) => write!(f, "1 1 {} {out} EQ", *b as u8),
+        }
+    }
+}
+
+/// A circuit in Bristol Fashion.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Circuit {
+    pub wires:   usize,
+    /// bits of each input
+    pub inputs:  Vec<usize>,
+    /// bits of each output
+    pub outputs: Vec<usize>,
+    pub gates:   Vec<Gate>,
+}
+
+struct Builder {
+    gates: Vec<Gate>,
+    wires: usize,
+}
+
+thread_local! {
+    static BUILDER: RefCell<Option<Builder>> = const { RefCell::new(None) };
+}
+
+fn emit(gate: impl FnOnce(usize) -> Gate) -> WireBit {
+    BUILDER.with(|b| {
+        let mut b = b.borrow_mut();
+        let b = b.as_mut().expect("WireBit gates can only be recorded inside Circuit::build");
+        let out = b.wires;
+        b.wires += 1;
+        b.gates.push(gate(out));
+        WireBit::Wire(out)
+    })
+}
+
+/// A bit that records the gates it passes through into the [`Circuit`] being built. Operations
+/// with constants are folded, as for [`crate::cost::CountedBit`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WireBit {
+    Constant(bool),
+    Wire(usize),
+}
+
+impl Bit for WireBit {
+    fn constant(b: bool) -> Self { WireBit::Constant(b) }
+}
+
+impl BitXor for WireBit {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        match (self, rhs) {
+            (WireBit::Constant(a), WireBit::Constant(b)) => WireBit::Constant(a ^ b),
+            (WireBit::Constant(c), w) | (w, WireBit::Constant(c)) =>
+                if c {
+                    !w
+                } else {
+                    w
+                },
+            (WireBit::Wire(a), WireBit::Wire(b)) => emit(|out| Gate::Xor(a, b, out)),
+        }
+    }
+}
+
+impl BitAnd for WireBit {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        match (self, rhs) {
+            (WireBit::Constant(a), WireBit::Constant(b)) => WireBit::Constant(a & b),
+            (WireBit::Constant(c), w) | (w, WireBit::Constant(c)) =>
+                if c {
+                    w
+                } else {
+                    WireBit::Constant(false)
+                },
+            (WireBit::Wire(a), WireBit::Wire(b)) => emit(|out| Gate::And(a, b, out)),
+        }
+    }
+}
+
+impl Not for WireBit {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        match self {
+            WireBit::Constant(c) => WireBit::Constant(!c),
+            WireBit::Wire(a) => emit(|out| Gate::Inv(a, out)),
+        }
+    }
+}
+
+impl Circuit {
+    /// Record the circuit computed by `f` over inputs of the given bit widths. Every output bit is
+    /// copied onto a fresh wire, so that outputs are the last wires even when they are constants,
+    /// inputs, or shared.
+    pub fn build(
+        inputs: &[usize],
+        f: impl FnOnce(&[Vec<WireBit>]) -> Vec<Vec<WireBit>>,
+    ) -> Circuit {
+        let mut next = 0;
+        let input_wires: Vec<Vec<WireBit>> = inputs
+            .iter()
+            .map(|n| {
+                next += n;
+                (next - n..next).map(WireBit::Wire).collect()
+            })
+            .collect();
+
+        BUILDER.with(|b| {
+            let previous = b.borrow_mut().replace(Builder { gates: vec![], wires: next });
+            assert!(previous.is_none(), "Circuit::build does not nest");
+        });
+        let outputs = f(&input_wires);
+        for bit in outputs.iter().flatten() {
+            match *bit {
+                WireBit::Constant(c) => emit(|out| Gate::Eq(c, out)),
+                WireBit::Wire(a) => emit(|out| Gate::Eqw(a, out)),
+            };
+        }
+        let Builder { gates, wires } = BUILDER.with(|b| b.borrow_mut().take()).unwrap();
+
+        Circuit {
+            wires,
+            inputs: inputs.to_vec(),
+            outputs: outputs.iter().map(Vec::len).collect(),
+            gates,
+        }
+    }
+
+    /// The number of AND gates, the cost in free-XOR garbling.
+    pub fn and_count(&self) -> usize {
+        self.gates.iter().filter(|g| matches!(g, Gate::And(..))).count()
+    }
+
+    /// The number of XOR gates.
+    pub fn xor_count(&self) -> usize {
+        self.gates.iter().filter(|g| matches!(g, Gate::Xor(..))).count()
+    }
+
+    /// Evaluate on one bit vector per input, returning one per output.
+    pub fn evaluate(&self, inputs: &[Vec<bool>]) -> anyhow::Result<Vec<Vec<bool>>> {
+        ensure!(
+            inputs.len() == self.inputs.len(),
+            "expected {} inputs, got {}",
+            self.inputs.len(),
+            inputs.len()
+        );
+        self.validate()?;
+        let mut values = vec![None; self.wires];
+        for (i, (input, width)) in inputs.iter().zip(&self.inputs).enumerate() {
+            ensure!(input.len() == *width, "input {i} has {} bits, expected {width}", input.len());
+        }
+        for (w, bit) in inputs.iter().flatten().enumerate() {
+            values[w] = Some(*bit);
+        }
+
+        let get = |values: &[Option<bool>], w: usize| -> anyhow::Result<bool> {
+            values
+                .get(w)
+                .copied()
+                .flatten()
+                .ok_or_else(|| anyhow!("wire {w} read before it is set"))
+        };
+        for gate in &self.gates {
+            let value = match *gate {
+                Gate::Xor(a, b, _) => get(&values, a)? ^ get(&values, b)?,
+                Gate::And(a, b, _) => get(&values, a)? & get(&values, b)?,
+                Gate::Inv(a, _) => !get(&values, a)?,
+                Gate::Eqw(a, _) => get(&values, a)?,
+                Gate::Eq(b, _) => b,
+            };
+            values[gate.output()] = Some(value);
+        }
+
+        let output_bits = self.outputs.iter().sum::<usize>();
+        let mut next =
+            self.wires.checked_sub(output_bits).context("more output bits than wires")?;
+        self.outputs
+            .iter()
+            .map(|n| {
+                next += n;
+                (next - n..next).map(|w| get(&values, w)).collect()
+            })
+            .collect()
+    }
+
+    /// Parse a Bristol Fashion file.
+    pub fn parse(s: &str) -> anyhow::Result<Circuit> {
+        let mut lines = s.lines().map(str::trim).filter(|l| !l.is_empty());
+        let mut header = || -> anyhow::Result<Vec<usize>> {
+            let line = lines.next().context("truncated header")?;
+            line.split_whitespace()
+                .map(|n| n.parse().with_context(|| format!("bad header {line:?}")))
+                .collect()
+        };
+
+        let counts = header()?;
+        let [n_gates, wires] = counts[..] else { bail!("expected `<gates> <wires>`") };
+        let inputs = header()?;
+        let outputs = header()?;
+        ensure!(inputs.first() == Some(&(inputs.len() - 1)), "bad input header");
+        ensure!(outputs.first() == Some(&(outputs.len() - 1)), "bad output header");
+
+        let gates = lines
+            .map(|line| {
+                let words: Vec<&str> = line.split_whitespace().collect();
+                let n = |i: usize| -> anyhow::Result<usize> {
+                    words
+                        .get(i)
+                        .context("truncated gate")?
+                        .parse()
+                        .with_context(|| format!("bad gate {line:?}"))
+                };
+                Ok(match words.last().copied() {
+                    Some("XOR") => Gate::Xor(n(2)?, n(3)?, n(4)?),
+                    Some("AND") => Gate::And(n(2)?, n(3)?, n(4)?),
+                    Some("INV") => Gate::Inv(n(2)?, n(3)?),
+                    Some("EQW") => Gate::Eqw(n(2)?, n(3)?),
+                    Some("EQ") => match n(2)? {
+                        c @ (0 | 1) => Gate::Eq(c == 1, n(3)?),
+                        c => bail!("EQ constant must be 0 or 1, got {c} in {line:?}"),
+                    },
+                    _ => bail!("unsupported gate {line:?}"),
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        ensure!(gates.len() == n_gates, "header declares {n_gates} gates, found {}", gates.len());
+
+        let circuit =
+            Circuit { wires, inputs: inputs[1..].to_vec(), outputs: outputs[1..].to_vec(), gates };
+        circuit.validate()?;
+        Ok(circuit)
+    }
+
+    /// Check that the inputs, the outputs and every gate fit in the declared wires, and that every
+    /// wire can be set by an input or a gate. The latter bounds [`Circuit::evaluate`]'s memory by
+    /// the size of the circuit rather than by a wire count read from an untrusted file.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let wires = self.wires;
+        let total = |widths: &[usize], what: &str| -> anyhow::Result<usize> {
+            widths
+                .iter()
+                .try_fold(0usize, |acc, n| acc.checked_add(*n))
+                .with_context(|| format!("{what} widths overflow"))
+        };
+        let (input_bits, output_bits) =
+            (total(&self.inputs, "input")?, total(&self.outputs, "output")?);
+        ensure!(input_bits <= wires, "{input_bits} input bits exceed {wires} wires");
+        ensure!(output_bits <= wires, "{output_bits} output bits exceed {wires} wires");
+        ensure!(
+            wires - input_bits <= self.gates.len(),
+            "{wires} wires, but {input_bits} input bits and {} gates set at most {}",
+            self.gates.len(),
+            input_bits + self.gates.len()
+        );
+        if let Some(gate) = self.gates.iter().find(|g| g.wires().iter().any(|w| *w >= wires)) {
+            bail!("gate {gate} uses a wire past {wires}");
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Circuit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths = |v: &[usize]| v.iter().map(|n| format!(" {n}")).collect::<String>();
+        writeln!(f, "{} {}", self.gates.len(), self.wires)?;
+        writeln!(f, "{}{}", self.inputs.len(), widths(&self.inputs))?;
+        writeln!(f, "{}{}", self.outputs.len(), widths(&self.outputs))?;
+        writeln!(f)?;
+        self.gates.iter().try_for_each(|g| writeln!(f, "{g}"))
+    }
+}
+
+/// Whether the hash key is an input of the circuit or compiled into it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashKey {
+    /// H is the first input.
+    Secret,
+    /// H is known to every party; multiplying by it costs no ANDs.
+    Public([u8; 16]),
+}
+
+/// gfmul as a circuit. Inputs are `[h, x]` for a secret key and `[x]` for a public one; the
+/// output is x·H.
+pub fn gfmul_circuit(strategy: CostStrategy, key: HashKey) -> anyhow::Result<Circuit> {
+    ghash_circuit(strategy, key, 1)
+}
+
+/// GHASH over `n_blocks` blocks as a circuit. Inputs are `h` for a secret key, then each block;
+/// the output is the GHASH digest.
+pub fn ghash_circuit(
+    strategy: CostStrategy,
+    key: HashKey,
+    n_blocks: usize,
+) -> anyhow::Result<Circuit> {
+    let table = matches!(strategy, CostStrategy::Table4 | CostStrategy::Table8);
+    ensure!(!(table && key == HashKey::Secret), "{strategy:?} needs a public hash key");
+
+    let secret = (key == HashKey::Secret) as usize;
+    Ok(Circuit::build(&vec![128; secret + n_blocks], |inputs| {
+        let (h, h_bits) = match key {
+            HashKey::Public(h) => (h, block_bits(h, WireBit::Constant)),
+            HashKey::Secret => ([0; 16], inputs[0].clone()),
+        };
+        vec![strategy.ghash(h, &h_bits, &inputs[secret..])]
+    }))
+}
+
+/// gfmul of two secret operands for free-XOR garbling: [`clmul_recursive_karatsuba`] down to
+/// `base`-bit products, then the reduction, which is XORs only. Inputs are `[h, x]`; the AND count
+/// achieved is [`Circuit::and_count`].
+pub fn and_minimized_gfmul_circuit(base: usize) -> Circuit {
+    Circuit::build(&[128, 128], |inputs| {
+        vec![reduce(clmul_recursive_karatsuba(&inputs[1], &inputs[0], base))]
+    })
+}
+
+/// The wire values of a block, wire `k` the coefficient of $x^k$.
+pub fn block_wires(block: [u8; 16]) -> Vec<bool> { parse_array_as_bits(block).to_vec() }
+
+/// The block carried by 128 output wires.
+pub fn wires_block(wires: &[bool]) -> [u8; 16] { bits_block(wires) }
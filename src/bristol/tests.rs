@@ -0,0 +1,130 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+use crate::{gfmul, ghash, tests::*};
+
+#[test]
+fn test_gfmul_circuit() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for strategy in CostStrategy::ALL {
+        let h: [u8; 16] = rng.gen();
+        let public = gfmul_circuit(strategy, HashKey::Public(h)).unwrap();
+        // table lookups are muxes on secret bits; the direct products fold to XORs
+        let table = matches!(strategy, CostStrategy::Table4 | CostStrategy::Table8);
+        assert_eq!(public.and_count() == 0, !table, "{strategy:?}");
+        for _ in 0..8 {
+            let x: [u8; 16] = rng.gen();
+            let out = public.evaluate(&[block_wires(x)]).unwrap();
+            assert_eq!(wires_block(&out[0]), gfmul(x, h), "{strategy:?}");
+        }
+    }
+
+    for strategy in
+        [CostStrategy::BitSerial, CostStrategy::Karatsuba, CostStrategy::RecursiveKaratsuba]
+    {
+        let secret = gfmul_circuit(strategy, HashKey::Secret).unwrap();
+        assert_eq!(secret.inputs, vec![128, 128]);
+        assert_eq!(secret.outputs, vec![128]);
+        for _ in 0..8 {
+            let (h, x): ([u8; 16], [u8; 16]) = (rng.gen(), rng.gen());
+            let out = secret.evaluate(&[block_wires(h), block_wires(x)]).unwrap();
+            assert_eq!(wires_block(&out[0]), gfmul(x, h), "{strategy:?}");
+        }
+        let out = secret.evaluate(&[block_wires(LONE), block_wires(X_1)]).unwrap();
+        assert_eq!(wires_block(&out[0]), X_1);
+    }
+    assert_eq!(
+        gfmul_circuit(CostStrategy::BitSerial, HashKey::Secret).unwrap().and_count(),
+        128 * 128
+    );
+    assert!(gfmul_circuit(CostStrategy::Table4, HashKey::Secret).is_err());
+}
+
+#[test]
+fn test_ghash_circuit() {
+    let circuit = ghash_circuit(CostStrategy::Karatsuba, HashKey::Secret, 2).unwrap();
+    let out = circuit.evaluate(&[block_wires(H), block_wires(X_1), block_wires(X_2)]).unwrap();
+    assert_eq!(wires_block(&out[0]), ghash(H, &[X_1, X_2]));
+
+    let circuit = ghash_circuit(CostStrategy::Table4, HashKey::Public(H), 2).unwrap();
+    let out = circuit.evaluate(&[block_wires(X_1), block_wires(X_2)]).unwrap();
+    assert_eq!(wires_block(&out[0]), ghash(H, &[X_1, X_2]));
+
+    // no blocks hashes to zero: the outputs are constant wires
+    let circuit = ghash_circuit(CostStrategy::BitSerial, HashKey::Public(H), 0).unwrap();
+    assert_eq!(wires_block(&circuit.evaluate(&[]).unwrap()[0]), [0; 16]);
+}
+
+#[test]
+fn test_wire_order() {
+    // wire k is the coefficient of x^k; x^0 is the top bit of byte 0
+    let wires = block_wires(LONE);
+    assert!(wires[0] && wires[1..].iter().all(|w| !w));
+    assert_eq!(wires_block(&wires), LONE);
+
+    // the output wires are the last wires of the circuit
+    let circuit = gfmul_circuit(CostStrategy::BitSerial, HashKey::Public(RTWO)).unwrap();
+    let last = circuit.gates.iter().rev().take(128).map(|g| g.output()).min().unwrap();
+    assert_eq!(last, circuit.wires - 128);
+}
+
+#[test]
+fn test_bristol_round_trip() {
+    let circuit = gfmul_circuit(CostStrategy::Karatsuba, HashKey::Secret).unwrap();
+    let text = circuit.to_string();
+    let mut lines = text.lines();
+    assert_eq!(lines.next().unwrap(), format!("{} {}", circuit.gates.len(), circuit.wires));
+    assert_eq!(lines.next().unwrap(), "2 128 128");
+    assert_eq!(lines.next().unwrap(), "1 128");
+    assert_eq!(Circuit::parse(&text).unwrap(), circuit);
+
+    let small = "3 5\n2 1 1\n1 1\n\n2 1 0 1 2 AND\n1 1 2 3 INV\n1 1 3 4 EQW\n";
+    let parsed = Circuit::parse(small).unwrap();
+    assert_eq!(parsed.evaluate(&[vec![true], vec![true]]).unwrap(), vec![vec![false]]);
+    assert_eq!(parsed.evaluate(&[vec![true], vec![false]]).unwrap(), vec![vec![true]]);
+
+    assert!(Circuit::parse("1 3\n2 1 1\n1 1\n\n2 1 0 1 2 MAND\n").is_err());
+    assert!(Circuit::parse("2 3\n2 1 1\n1 1\n\n2 1 0 1 2 AND\n").is_err());
+    assert!(parsed.evaluate(&[vec![true]]).is_err());
+
+    // wires that do not fit the declared count
+    assert!(Circuit::parse("0 1\n1 2\n1 0\n").is_err());
+    assert!(Circuit::parse("0 1\n1 1\n1 5\n").is_err());
+    assert!(Circuit::parse("1 3\n2 1 1\n1 1\n\n2 1 0 3 2 AND\n").is_err());
+    assert!(Circuit::parse("1 3\n2 1 1\n1 1\n\n1 1 0 3 INV\n").is_err());
+    let mut built = parsed.clone();
+    built.wires = 2;
+    assert!(built.evaluate(&[vec![true], vec![true]]).is_err());
+
+    // hostile counts are errors, not overflows or huge allocations
+    let max = usize::MAX;
+    assert!(Circuit::parse(&format!("0 {max}\n1 1\n1 1\n")).is_err());
+    assert!(Circuit::parse(&format!("0 1\n2 {max} 2\n1 1\n")).is_err());
+    assert!(Circuit::parse(&format!("0 1\n1 1\n2 {max} 2\n")).is_err());
+    built.wires = max;
+    assert!(built.evaluate(&[vec![true], vec![true]]).is_err());
+    built.wires = 3;
+    built.outputs = vec![max, 2];
+    assert!(built.evaluate(&[vec![true], vec![true]]).is_err());
+
+    // EQ takes a constant bit
+    assert_eq!(Circuit::parse("1 2\n1 1\n1 1\n\n1 1 1 1 EQ\n").unwrap().gates, [Gate::Eq(true, 1)]);
+    assert!(Circuit::parse("1 2\n1 1\n1 1\n\n1 1 2 1 EQ\n").is_err());
+}
+
+#[test]
+fn test_and_minimized_gfmul_circuit() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for (base, ands) in [(1, 2187), (2, 4 * 729), (4, 16 * 243), (16, 256 * 27), (128, 16384)] {
+        let circuit = and_minimized_gfmul_circuit(base);
+        assert_eq!(circuit.and_count(), ands, "base {base}");
+        for (h, x) in [(LONE, X_1), (H, RTHREE0), (rng.gen(), rng.gen())] {
+            let out = circuit.evaluate(&[block_wires(h), block_wires(x)]).unwrap();
+            assert_eq!(wires_block(&out[0]), gfmul(x, h), "base {base}");
+        }
+    }
+
+    // the reduction adds no ANDs
+    let circuit = gfmul_circuit(CostStrategy::RecursiveKaratsuba, HashKey::Secret).unwrap();
+    assert_eq!(circuit.and_count(), 2187, "{} XOR", circuit.xor_count());
+}
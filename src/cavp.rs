@@ -0,0 +1,140 @@
+//! NIST CAVP GCM test vectors: a parser for the `gcmEncryptExtIV*.rsp` and `gcmDecrypt*.rsp`
+//! response files and a runner that checks [`AesGcm`], and so the crate's GHASH, on every entry.
+//!
+//! The files are read from a local directory, so the suite runs offline. `vectors/cavp` holds a
+//! sample; drop the full files from the CAVP `gcmtestvectors.zip` in beside it to run them all.
+
+use std::{fs, path::Path};
+
+use anyhow::{bail, ensure, Context, Result};
+
+use crate::AesGcm;
+
+#[cfg(test)] mod tests;
+
+/// One `Count = ...` entry of a response file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CavpVector {
+    pub count: usize,
+    pub key:   Vec<u8>,
+    pub iv:    Vec<u8>,
+    /// `None` for decrypt entries marked `FAIL`
+    pub pt:    Option<Vec<u8>>,
+    pub aad:   Vec<u8>,
+    pub ct:    Vec<u8>,
+    /// truncated to the section's `Taglen`
+    pub tag:   Vec<u8>,
+    /// the tag must be rejected
+    pub fail:  bool,
+}
+
+/// Parse a GCM response file. Comments and `[...]` section headers are skipped, since every
+/// entry carries its own lengths.
+pub fn parse_rsp(s: &str) -> Result<Vec<CavpVector>> {
+    let mut vectors: Vec<CavpVector> = vec![];
+    for (n, line) in s.lines().enumerate().map(|(n, l)| (n + 1, l.trim())) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+            continue;
+        }
+        if line == "FAIL" {
+            let vector =
+                vectors.last_mut().with_context(|| format!("line {n}: FAIL outside an entry"))?;
+            vector.fail = true;
+            continue;
+        }
+
+        let (name, value) =
+            line.split_once('=').with_context(|| format!("line {n}: expected `Name = value`"))?;
+        let value = value.trim();
+        if name.trim() == "Count" {
+            let count = value.parse().with_context(|| format!("line {n}: bad Count"))?;
+            vectors.push(CavpVector { count, ..Default::default() });
+            continue;
+        }
+
+        let vector =
+            vectors.last_mut().with_context(|| format!("line {n}: {name} before Count"))?;
+        let bytes =
+            hex::decode(value).with_context(|| format!("line {n}: bad hex in {}", name.trim()))?;
+        match name.trim() {
+            "Key" => vector.key = bytes,
+            "IV" => vector.iv = bytes,
+            "PT" => vector.pt = Some(bytes),
+            "AAD" => vector.aad = bytes,
+            "CT" => vector.ct = bytes,
+            "Tag" => vector.tag = bytes,
+            other => bail!("line {n}: unknown field {other}"),
+        }
+    }
+
+    for v in &vectors {
+        ensure!(
+            v.fail != v.pt.is_some(),
+            "Count = {}: expected exactly one of PT and FAIL",
+            v.count
+        );
+    }
+    Ok(vectors)
+}
+
+/// Check one entry: encrypting PT must give CT and the tag, and opening CT must give PT, or fail
+/// for `FAIL` entries.
+pub fn check_vector(v: &CavpVector) -> Result<()> {
+    let gcm = AesGcm::new(&v.key)?;
+    let opened = gcm.open(&v.iv, &v.aad, &v.ct, &v.tag);
+    let Some(pt) = &v.pt else {
+        ensure!(opened.is_err(), "accepted a tag marked FAIL");
+        return Ok(());
+    };
+
+    let (ct, tag) = gcm.seal(&v.iv, &v.aad, pt)?;
+    ensure!(ct == v.ct, "ciphertext {} != {}", hex::encode(ct), hex::encode(&v.ct));
+    ensure!(
+        tag.get(..v.tag.len()) == Some(&v.tag[..]),
+        "tag {} != {}",
+        hex::encode(tag),
+        hex::encode(&v.tag)
+    );
+    ensure!(opened? == *pt, "decryption mismatch");
+    Ok(())
+}
+
+/// The results of one response file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CavpReport {
+    pub file:     String,
+    pub passed:   usize,
+    /// (Count, error) of each failing entry; counts restart in each section
+    pub failures: Vec<(usize, String)>,
+}
+
+/// Run every `gcmEncryptExtIV*.rsp` and `gcmDecrypt*.rsp` file in `dir`, in name order.
+pub fn run_dir(dir: impl AsRef<Path>) -> Result<Vec<CavpReport>> {
+    let dir = dir.as_ref();
+    let mut files: Vec<_> = fs::read_dir(dir)
+        .with_context(|| format!("reading {}", dir.display()))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    files.retain(|p| {
+        let name = p.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        (name.starts_with("gcmEncryptExtIV") || name.starts_with("gcmDecrypt"))
+            && name.ends_with(".rsp")
+    });
+    files.sort();
+
+    files
+        .into_iter()
+        .map(|path| {
+            let text =
+                fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+            let vectors =
+                parse_rsp(&text).with_context(|| format!("parsing {}", path.display()))?;
+            let failures: Vec<_> = vectors
+                .iter()
+                .filter_map(|v| check_vector(v).err().map(|e| (v.count, format!("{e:#}"))))
+                .collect();
+            let file = path.file_name().unwrap().to_string_lossy().into_owned();
+            Ok(CavpReport { file, passed: vectors.len() - failures.len(), failures })
+        })
+        .collect()
+}
@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+
+use super::*;
+
+/// `CAVP_DIR` if set, for the full NIST files, else the sample in `vectors/cavp`.
+fn vector_dir() -> PathBuf {
+    std::env::var_os("CAVP_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("vectors/cavp"))
+}
+
+#[test]
+fn test_parse_rsp() {
+    let text = "# comment\n[Taglen = 96]\n\nCount = 3\nKey = 00\nIV = 0102\nCT = \nAAD = ff\nTag \
+                = aabb\nFAIL\n\nCount = 4\nKey = 00\nIV = 01\nPT = 10\nAAD = \nCT = 20\nTag = cc\n";
+    let vectors = parse_rsp(text).unwrap();
+    assert_eq!(vectors.len(), 2);
+    assert_eq!(vectors[0], CavpVector {
+        count: 3,
+        key:   vec![0],
+        iv:    vec![1, 2],
+        pt:    None,
+        aad:   vec![0xff],
+        ct:    vec![],
+        tag:   vec![0xaa, 0xbb],
+        fail:  true,
+    });
+    assert_eq!(vectors[1].pt, Some(vec![0x10]));
+    assert!(!vectors[1].fail);
+
+    assert!(parse_rsp("Key = 00\n").is_err());
+    assert!(parse_rsp("Count = 0\nKey = 0g\n").is_err());
+    assert!(parse_rsp("Count = 0\nNonce = 00\n").is_err());
+    assert!(parse_rsp("FAIL\n").is_err());
+    // neither PT nor FAIL
+    assert!(parse_rsp("Count = 0\nKey = 00\n").is_err());
+}
+
+#[test]
+fn test_check_vector_rejects() {
+    let gcm = AesGcm::new(&[7; 16]).unwrap();
+    let (ct, tag) = gcm.seal(&[1; 12], b"aad", b"pt").unwrap();
+    let good = CavpVector {
+        key: vec![7; 16],
+        iv: vec![1; 12],
+        pt: Some(b"pt".to_vec()),
+        aad: b"aad".to_vec(),
+        ct,
+        tag: tag[..12].to_vec(),
+        ..Default::default()
+    };
+    check_vector(&good).unwrap();
+
+    let mut bad = good.clone();
+    bad.ct[0] ^= 1;
+    assert!(check_vector(&bad).is_err());
+
+    // a good tag marked FAIL is a failure of the entry
+    let mut marked = good.clone();
+    (marked.pt, marked.fail) = (None, true);
+    assert!(check_vector(&marked).is_err());
+    marked.tag[0] ^= 1;
+    check_vector(&marked).unwrap();
+
+    // a Tag longer than a block is an error, not a panic
+    let mut long = good.clone();
+    long.tag = vec![0; 17];
+    assert!(check_vector(&long).is_err());
+}
+
+#[test]
+fn test_cavp_vectors() {
+    let reports = run_dir(vector_dir()).unwrap();
+    assert!(!reports.is_empty());
+    for report in &reports {
+        assert!(report.failures.is_empty(), "{}: {:?}", report.file, report.failures);
+        assert!(report.passed > 0, "{}: no vectors", report.file);
+    }
+}
+
+#[test]
+fn test_sample_coverage() {
+    // the bundled sample reaches the IV-hashing, AAD, truncated-tag and FAIL paths
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("vectors/cavp");
+    let read = |name: &str| parse_rsp(&std::fs::read_to_string(dir.join(name)).unwrap()).unwrap();
+    let vectors: Vec<_> = [read("gcmEncryptExtIV128.rsp"), read("gcmDecrypt128.rsp")].concat();
+
+    assert!(vectors.iter().any(|v| v.iv.len() < 12) && vectors.iter().any(|v| v.iv.len() > 12));
+    assert!(vectors
+        .iter()
+        .any(|v| !v.aad.is_empty() && v.pt.as_ref().is_some_and(|pt| !pt.is_empty())));
+    assert!(vectors.iter().any(|v| v.tag.len() == 15) && vectors.iter().any(|v| v.tag.len() == 13));
+    assert!(vectors.iter().any(|v| v.fail));
+    vectors.iter().for_each(|v| check_vector(v).unwrap());
+}
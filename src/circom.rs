@@ -0,0 +1,77 @@
+//! Circom input JSON for gfmul and ghash.
+//!
+//! Circuits take every 128-bit value as an array of 128 bit strings, e.g. `["1", "0", ...]`.
+//! Which bit goes first is the recurring source of mismatches, so the order is explicit:
+//!
+//! - [`BitOrder::Ghash`]: index `i` is the coefficient of $x^i$, as from `parse_array_as_bits`.
+//!   Since GHASH stores $x^0$ in the most significant bit of byte 0, this is the same as reading
+//!   the bytes in order, each most significant bit first.
+//! - [`BitOrder::LsbFirstBytes`]: the bytes in order, each least significant bit first, as from
+//!   circom's `Num2Bits(8)` applied to each byte.
+
+use serde_json::{json, Value};
+
+use crate::{
+    gfmul, ghash, parse_array_as_bits, parse_u128_as_array, reverse_byte,
+    witness::{Witness, WitnessValue},
+};
+
+#[cfg(test)] mod tests;
+
+/// The order in which the 128 bits of a block are listed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BitOrder {
+    /// Index `i` is the coefficient of $x^i$; equivalently, bytes in order, MSB first.
+    #[default]
+    Ghash,
+    /// Bytes in order, LSB first.
+    LsbFirstBytes,
+}
+
+impl BitOrder {
+    /// The bits of a GHASH-convention block in this order.
+    pub fn bits(self, block: [u8; 16]) -> [bool; 128] {
+        match self {
+            BitOrder::Ghash => parse_array_as_bits(block),
+            // reversing each byte turns MSB-first into LSB-first
+            BitOrder::LsbFirstBytes => parse_array_as_bits(block.map(reverse_byte)),
+        }
+    }
+
+    /// The block as a JSON array of `"0"`/`"1"` strings in this order.
+    pub fn to_json(self, block: [u8; 16]) -> Value {
+        self.bits(block).iter().map(|b| if *b { "1" } else { "0" }).collect()
+    }
+}
+
+/// Circom input for `a * b`: `{"a": [...], "b": [...], "out": [...]}`, with the expected product.
+pub fn gfmul_input_json(a: [u8; 16], b: [u8; 16], order: BitOrder) -> Value {
+    json!({
+        "a": order.to_json(a),
+        "b": order.to_json(b),
+        "out": order.to_json(gfmul(a, b)),
+    })
+}
+
+/// Circom input for GHASH: `{"h": [...], "blocks": [[...], ...], "out": [...]}`, with the expected
+/// hash.
+pub fn ghash_input_json(h: [u8; 16], blocks: &[[u8; 16]], order: BitOrder) -> Value {
+    json!({
+        "h": order.to_json(h),
+        "blocks": blocks.iter().map(|block| order.to_json(*block)).collect::<Vec<_>>(),
+        "out": order.to_json(ghash(h, blocks)),
+    })
+}
+
+/// Every signal of a [`Witness`] keyed by its name: bits as `"0"`/`"1"`, field elements as bit
+/// arrays in `order`. For diffing against circuit witnesses.
+pub fn witness_json(witness: &Witness, order: BitOrder) -> Value {
+    let signals = witness.iter().map(|(name, value)| {
+        let value = match value {
+            WitnessValue::Bit(b) => json!(if b { "1" } else { "0" }),
+            WitnessValue::Field(n) => order.to_json(parse_u128_as_array(n)),
+        };
+        (name.to_string(), value)
+    });
+    Value::Object(signals.collect())
+}
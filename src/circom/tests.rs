@@ -0,0 +1,75 @@
+use hex_literal::hex;
+use serde_json::json;
+
+use super::*;
+use crate::{gfmul_with_witness, tests::*};
+
+/// parse a JSON bit-string array back into bits
+fn parse_bits(v: &Value) -> Vec<bool> {
+    v.as_array().unwrap().iter().map(|b| b.as_str().unwrap() == "1").collect()
+}
+
+#[test]
+fn test_ghash_order_is_msb_first_bytes() {
+    for block in [LONE, RONE, LC, RTHREE0, H, X_1] {
+        let msb_first: Vec<bool> =
+            block.iter().flat_map(|byte| (0..8).rev().map(move |j| byte & (1 << j) != 0)).collect();
+        assert_eq!(BitOrder::Ghash.bits(block).to_vec(), msb_first);
+    }
+}
+
+#[test]
+fn test_bit_orders() {
+    // x^0
+    assert!(BitOrder::Ghash.bits(LONE)[0]);
+    assert!(BitOrder::LsbFirstBytes.bits(LONE)[7]);
+    // x^127
+    assert!(BitOrder::Ghash.bits(RONE)[127]);
+    assert!(BitOrder::LsbFirstBytes.bits(RONE)[120]);
+    // x^2 + x^3
+    assert_eq!(BitOrder::Ghash.bits(LC).iter().filter(|b| **b).count(), 2);
+    assert!(BitOrder::Ghash.bits(LC)[2] && BitOrder::Ghash.bits(LC)[3]);
+    assert!(BitOrder::LsbFirstBytes.bits(LC)[4] && BitOrder::LsbFirstBytes.bits(LC)[5]);
+
+    let json = BitOrder::LsbFirstBytes.to_json(hex!("01000000000000000000000000000080"));
+    let mut expected = vec!["0"; 128];
+    expected[0] = "1";
+    expected[127] = "1";
+    assert_eq!(json, json!(expected));
+}
+
+#[test]
+fn test_gfmul_input_json() {
+    for order in [BitOrder::Ghash, BitOrder::LsbFirstBytes] {
+        let v = gfmul_input_json(H, X_1, order);
+        assert_eq!(parse_bits(&v["a"]), order.bits(H));
+        assert_eq!(parse_bits(&v["b"]), order.bits(X_1));
+        assert_eq!(parse_bits(&v["out"]), order.bits(gfmul(H, X_1)));
+    }
+}
+
+#[test]
+fn test_ghash_input_json() {
+    let v = ghash_input_json(H, &[X_1, X_2], BitOrder::Ghash);
+    assert_eq!(parse_bits(&v["h"]), parse_array_as_bits(H));
+    assert_eq!(v["blocks"].as_array().unwrap().len(), 2);
+    assert_eq!(parse_bits(&v["blocks"][1]), parse_array_as_bits(X_2));
+    assert_eq!(parse_bits(&v["out"]), parse_array_as_bits(ghash(H, &[X_1, X_2])));
+
+    // round trip through a string, as written to disk
+    let s = serde_json::to_string_pretty(&v).unwrap();
+    assert_eq!(serde_json::from_str::<Value>(&s).unwrap(), v);
+    assert!(s.contains("\"0\""));
+}
+
+#[test]
+fn test_witness_json() {
+    let (out, w) = gfmul_with_witness(LC, RTHREE);
+    // through the crate root, as downstream users call it
+    let v = crate::witness_json(&w, BitOrder::Ghash);
+    assert_eq!(v.as_object().unwrap().len(), w.len());
+    assert_eq!(parse_bits(&v["a"]), parse_array_as_bits(LC));
+    assert_eq!(parse_bits(&v["out"]), parse_array_as_bits(out));
+    assert_eq!(v["b_bits[126]"], json!("1"));
+    assert_eq!(v["b_bits[0]"], json!("0"));
+}
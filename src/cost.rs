@@ -0,0 +1,361 @@
+//! Operation counts of the multiplication strategies, as circuit and MPC cost estimates.
+//!
+//! The strategies are written once over a generic [`Bit`], so they run on plain `bool`s for
+//! testing and on [`CountedBit`]s for measurement. A [`CountedBit`] counts every AND, XOR and NOT
+//! it takes part in, and tracks multiplicative (AND) depth. Operations with a public constant are
+//! folded and free, as a circuit compiler would treat them; so are the table entries of the
+//! table strategies, where H is public.
+
+use core::{
+    cell::Cell,
+    fmt,
+    ops::{BitAnd, BitXor, Not},
+};
+
+use crate::{
+    parse_array_as_bits, parse_u128_as_array,
+    table::{GhashKey, TableSize, REDUCE_4, REDUCE_8},
+};
+
+#[cfg(test)] mod tests;
+
+/// A boolean that the multiplication strategies can run over.
+pub trait Bit: Copy + BitXor<Output = Self> + BitAnd<Output = Self> + Not<Output = Self> {
+    /// A public constant.
+    fn constant(b: bool) -> Self;
+}
+
+impl Bit for bool {
+    fn constant(b: bool) -> Self { b }
+}
+
+thread_local! {
+    static COUNTS: Cell<(usize, usize, usize)> = const { Cell::new((0, 0, 0)) };
+}
+
+fn count(and: usize, xor: usize, not: usize) {
+    COUNTS.with(|c| {
+        let (a, x, n) = c.get();
+        c.set((a + and, x + xor, n + not));
+    });
+}
+
+/// A bit that counts the gates it passes through, on the current thread; see [`measure`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CountedBit {
+    value:    bool,
+    /// AND depth
+    depth:    u32,
+    constant: bool,
+}
+
+impl CountedBit {
+    /// A secret input wire.
+    pub fn input(value: bool) -> Self { Self { value, depth: 0, constant: false } }
+
+    /// The value carried.
+    pub fn value(self) -> bool { self.value }
+
+    /// The number of ANDs on the longest path from an input.
+    pub fn depth(self) -> u32 { self.depth }
+}
+
+impl Bit for CountedBit {
+    fn constant(value: bool) -> Self { Self { value, depth: 0, constant: true } }
+}
+
+impl BitXor for CountedBit {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        match (self.constant, rhs.constant) {
+            (true, true) => Self::constant(self.value ^ rhs.value),
+            (true, false) if self.value => !rhs,
+            (true, false) => rhs,
+            (false, true) if rhs.value => !self,
+            (false, true) => self,
+            (false, false) => {
+                count(0, 1, 0);
+                Self {
+                    value:    self.value ^ rhs.value,
+                    depth:    self.depth.max(rhs.depth),
+                    constant: false,
+                }
+            },
+        }
+    }
+}
+
+impl BitAnd for CountedBit {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        match (self.constant, rhs.constant) {
+            (true, true) => Self::constant(self.value & rhs.value),
+            (true, false) =>
+                if self.value {
+                    rhs
+                } else {
+                    Self::constant(false)
+                },
+            (false, true) =>
+                if rhs.value {
+                    self
+                } else {
+                    Self::constant(false)
+                },
+            (false, false) => {
+                count(1, 0, 0);
+                let depth = self.depth.max(rhs.depth) + 1;
+                Self { value: self.value & rhs.value, depth, constant: false }
+            },
+        }
+    }
+}
+
+impl Not for CountedBit {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        if !self.constant {
+            count(0, 0, 1);
+        }
+        Self { value: !self.value, ..self }
+    }
+}
+
+/// Gate counts and multiplicative depth of a circuit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Cost {
+    pub and:   usize,
+    pub xor:   usize,
+    pub not:   usize,
+    /// AND depth of the deepest output
+    pub depth: u32,
+}
+
+impl fmt::Display for Cost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AND {:>6}  XOR {:>6}  NOT {:>4}  depth {:>3}",
+            self.and, self.xor, self.not, self.depth
+        )
+    }
+}
+
+/// Run `f` over [`CountedBit`]s and count the gates it uses on this thread.
+pub fn measure(f: impl FnOnce() -> Vec<CountedBit>) -> Cost {
+    let before = COUNTS.with(Cell::get);
+    let outputs = f();
+    let (and, xor, not) = COUNTS.with(Cell::get);
+    let depth = outputs.iter().map(|b| b.depth).max().unwrap_or(0);
+    Cost { and: and - before.0, xor: xor - before.1, not: not - before.2, depth }
+}
+
+/// The bits of a GHASH-convention block, index `i` the coefficient of $x^i$.
+pub fn block_bits<B: Bit>(block: [u8; 16], wire: impl Fn(bool) -> B) -> Vec<B> {
+    parse_array_as_bits(block).into_iter().map(wire).collect()
+}
+
+/// Read a block back out of 128 bits.
+pub fn bits_block(bits: &[bool]) -> [u8; 16] {
+    parse_u128_as_array(bits.iter().rev().fold(0, |acc, b| (acc << 1) | *b as u128))
+}
+
+/// Carryless product, one AND per pair of bits, as in [`crate::gfmul`].
+pub fn clmul_bit_serial<B: Bit>(a: &[B], b: &[B]) -> Vec<B> {
+    let mut columns = vec![B::constant(false); a.len() + b.len()];
+    for (i, a_i) in a.iter().enumerate() {
+        for (j, b_j) in b.iter().enumerate() {
+            columns[i + j] = columns[i + j] ^ (*a_i & *b_j);
+        }
+    }
+    columns
+}
+
+/// Carryless product of 128-bit operands with one level of Karatsuba, as in
+/// [`crate::karatsuba_gfmul`].
+pub fn clmul_karatsuba<B: Bit>(a: &[B], b: &[B]) -> Vec<B> {
+    let (ar, al) = a.split_at(64);
+    let (br, bl) = b.split_at(64);
+    let xor = |x: &[B], y: &[B]| x.iter().zip(y).map(|(x, y)| *x ^ *y).collect::<Vec<_>>();
+
+    let rr = clmul_bit_serial(ar, br);
+    let ll = clmul_bit_serial(al, bl);
+    let lr = xor(&xor(&clmul_bit_serial(&xor(al, ar), &xor(bl, br)), &ll), &rr);
+
+    let mut columns = vec![B::constant(false); 256];
+    for k in 0..128 {
+        columns[k] = columns[k] ^ rr[k];
+        columns[k + 128] = columns[k + 128] ^ ll[k];
+        columns[k + 64] = columns[k + 64] ^ lr[k];
+    }
+    columns
+}
+
+/// Carryless product with Karatsuba applied recursively until operands are at most `base` bits,
+/// which are multiplied bit-serially. Operands have equal length; odd halves are padded with
+/// constant zeros, which cost nothing.
+///
+/// Each level trades one of four half-size products for XORs, so `base = 1` takes $3^7 = 2187$
+/// ANDs at 128 bits, against 16384 bit-serially.
+pub fn clmul_recursive_karatsuba<B: Bit>(a: &[B], b: &[B], base: usize) -> Vec<B> {
+    assert_eq!(a.len(), b.len());
+    let n = a.len();
+    if n <= base.max(1) {
+        return clmul_bit_serial(a, b);
+    }
+
+    let m = n.div_ceil(2);
+    let pad = |x: &[B]| {
+        let mut x = x.to_vec();
+        x.resize(m, B::constant(false));
+        x
+    };
+    let xor = |x: &[B], y: &[B]| x.iter().zip(y).map(|(x, y)| *x ^ *y).collect::<Vec<_>>();
+    let (a0, a1) = (&a[..m], pad(&a[m..]));
+    let (b0, b1) = (&b[..m], pad(&b[m..]));
+
+    let low = clmul_recursive_karatsuba(a0, b0, base);
+    let high = clmul_recursive_karatsuba(&a1, &b1, base);
+    let mid = clmul_recursive_karatsuba(&xor(a0, &a1), &xor(b0, &b1), base);
+    let mid = xor(&xor(&mid, &low), &high);
+
+    let mut columns = vec![B::constant(false); 2 * n + 2];
+    for k in 0..2 * m {
+        columns[k] = columns[k] ^ low[k];
+        columns[k + m] = columns[k + m] ^ mid[k];
+        columns[k + 2 * m] = columns[k + 2 * m] ^ high[k];
+    }
+    columns.truncate(2 * n);
+    columns
+}
+
+/// Fold columns 128..256 into 0..128 with x^128 = x^7 + x^2 + x + 1, as `galois_reduce`.
+pub fn reduce<B: Bit>(mut columns: Vec<B>) -> Vec<B> {
+    columns.resize(256, B::constant(false));
+    for k in (128..256).rev() {
+        for j in [0, 1, 2, 7] {
+            columns[k - 128 + j] = columns[k - 128 + j] ^ columns[k];
+        }
+    }
+    columns.truncate(128);
+    columns
+}
+
+/// Select `entries[index]` for a secret `index` (LSB first) with a tree of muxes.
+fn lookup<B: Bit>(entries: &[u128], index: &[B]) -> Vec<B> {
+    let mut level: Vec<Vec<B>> = entries
+        .iter()
+        .map(|e| (0..128).map(|i| B::constant((e >> i) & 1 == 1)).collect())
+        .collect();
+    for s in index {
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                pair[0].iter().zip(&pair[1]).map(|(e0, e1)| *e0 ^ (*s & (*e0 ^ *e1))).collect()
+            })
+            .collect();
+    }
+    level.pop().unwrap()
+}
+
+/// x·H for a public H with a Shoup table, as [`GhashKey::mul`]: every table lookup and reduction
+/// lookup is a mux tree over public entries.
+pub fn table_mul<B: Bit>(x: &[B], key: &GhashKey) -> Vec<B> {
+    let w = key.size().bits();
+    let reduce_table: &[u128] = match key.size() {
+        TableSize::Four => &REDUCE_4,
+        TableSize::Eight => &REDUCE_8,
+    };
+
+    let mut z = vec![B::constant(false); 128];
+    for k in (0..128 / w).rev() {
+        let spill = lookup(reduce_table, &z[128 - w..]);
+        let entry = lookup(key.table(), &x[k * w..(k + 1) * w]);
+        let shifted: Vec<B> =
+            (0..128).map(|i| if i < w { B::constant(false) } else { z[i - w] }).collect();
+        z = (0..128).map(|i| shifted[i] ^ spill[i] ^ entry[i]).collect();
+    }
+    z
+}
+
+/// A multiplication strategy to cost.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CostStrategy {
+    /// [`clmul_bit_serial`], both operands secret.
+    BitSerial,
+    /// [`clmul_karatsuba`], both operands secret.
+    Karatsuba,
+    /// [`clmul_recursive_karatsuba`] down to single bits, both operands secret: the fewest ANDs.
+    RecursiveKaratsuba,
+    /// [`table_mul`] with 4-bit tables; H is public.
+    Table4,
+    /// [`table_mul`] with 8-bit tables; H is public.
+    Table8,
+}
+
+impl CostStrategy {
+    /// Every strategy, for the report.
+    pub const ALL: [CostStrategy; 5] = [
+        CostStrategy::BitSerial,
+        CostStrategy::Karatsuba,
+        CostStrategy::RecursiveKaratsuba,
+        CostStrategy::Table4,
+        CostStrategy::Table8,
+    ];
+
+    /// Whether the strategy assumes a public H. The table strategies look up multiples of H, so
+    /// they only apply when H is known, and then a direct multiply by H costs no ANDs at all.
+    pub fn public_h(self) -> bool { matches!(self, CostStrategy::Table4 | CostStrategy::Table8) }
+
+    /// Multiply `x` by `h`; `h_bits` are the bits of `h` for the strategies where H is secret.
+    pub fn mul<B: Bit>(self, x: &[B], h: [u8; 16], h_bits: &[B]) -> Vec<B> {
+        match self {
+            CostStrategy::BitSerial => reduce(clmul_bit_serial(x, h_bits)),
+            CostStrategy::Karatsuba => reduce(clmul_karatsuba(x, h_bits)),
+            CostStrategy::RecursiveKaratsuba => reduce(clmul_recursive_karatsuba(x, h_bits, 1)),
+            CostStrategy::Table4 => table_mul(x, &GhashKey::new(h, TableSize::Four)),
+            CostStrategy::Table8 => table_mul(x, &GhashKey::new(h, TableSize::Eight)),
+        }
+    }
+
+    /// GHASH of `blocks` under `h`.
+    pub fn ghash<B: Bit>(self, h: [u8; 16], h_bits: &[B], blocks: &[Vec<B>]) -> Vec<B> {
+        blocks.iter().fold(vec![B::constant(false); 128], |y, block| {
+            let y: Vec<B> = y.iter().zip(block).map(|(y, x)| *y ^ *x).collect();
+            self.mul(&y, h, h_bits)
+        })
+    }
+}
+
+/// The cost of one gfmul with `strategy`, over secret operands.
+pub fn gfmul_cost(strategy: CostStrategy, a: [u8; 16], h: [u8; 16]) -> Cost {
+    measure(|| {
+        strategy.mul(&block_bits(a, CountedBit::input), h, &block_bits(h, CountedBit::input))
+    })
+}
+
+/// The cost of GHASH over secret `blocks` with `strategy`.
+pub fn ghash_cost(strategy: CostStrategy, h: [u8; 16], blocks: &[[u8; 16]]) -> Cost {
+    let blocks: Vec<Vec<CountedBit>> =
+        blocks.iter().map(|b| block_bits(*b, CountedBit::input)).collect();
+    measure(|| strategy.ghash(h, &block_bits(h, CountedBit::input), &blocks))
+}
+
+/// One line per strategy: the cost of a gfmul and of an `n_blocks`-block GHASH, labelled with
+/// whether the strategy assumes a secret or a public H. Only the secret-H rows compare with each
+/// other: with a public H, multiplying by H directly costs no ANDs.
+pub fn cost_report(h: [u8; 16], n_blocks: usize) -> String {
+    let blocks = vec![[0x5a; 16]; n_blocks];
+    CostStrategy::ALL
+        .iter()
+        .map(|s| {
+            let (mul, ghash) = (gfmul_cost(*s, [0xa5; 16], h), ghash_cost(*s, h, &blocks));
+            let key = if s.public_h() { "public H" } else { "secret H" };
+            let name = format!("{s:?}");
+            format!("{name:<18} {key}  gfmul: {mul}  |  ghash({n_blocks}): {ghash}\n")
+        })
+        .collect()
+}
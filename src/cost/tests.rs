@@ -0,0 +1,110 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+use crate::{gfmul, ghash, tests::*};
+
+#[test]
+fn test_strategies_over_bool() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let random: ([u8; 16], [u8; 16]) = (rng.gen(), rng.gen());
+    for strategy in CostStrategy::ALL {
+        for (a, h) in [(LONE, RONE), (LC, RTHREE), (RTHREE0, RTHREE0), (X_1, H), random] {
+            let out = strategy.mul(&block_bits(a, |b| b), h, &block_bits(h, |b| b));
+            assert_eq!(bits_block(&out), gfmul(a, h), "{strategy:?}");
+        }
+        let blocks = [X_1, X_2].map(|b| block_bits(b, |b| b));
+        let out = strategy.ghash(H, &block_bits(H, |b| b), &blocks);
+        assert_eq!(bits_block(&out), ghash(H, &[X_1, X_2]), "{strategy:?}");
+    }
+}
+
+#[test]
+fn test_counted_values() {
+    for strategy in CostStrategy::ALL {
+        let out =
+            strategy.mul(&block_bits(X_1, CountedBit::input), H, &block_bits(H, CountedBit::input));
+        let out: Vec<bool> = out.iter().map(|b| b.value()).collect();
+        assert_eq!(bits_block(&out), gfmul(X_1, H), "{strategy:?}");
+    }
+}
+
+#[test]
+fn test_counted_gates() {
+    let (a, b) = (CountedBit::input(true), CountedBit::input(false));
+    let (zero, one) = (CountedBit::constant(false), CountedBit::constant(true));
+
+    assert_eq!(measure(|| vec![a & b]), Cost { and: 1, depth: 1, ..Cost::default() });
+    assert_eq!(measure(|| vec![a ^ b]), Cost { xor: 1, ..Cost::default() });
+    assert_eq!(measure(|| vec![!a]), Cost { not: 1, ..Cost::default() });
+    assert_eq!(measure(|| vec![(a & b) & a]), Cost { and: 2, depth: 2, ..Cost::default() });
+
+    // constants fold
+    assert_eq!(measure(|| vec![a & zero, a & one, a ^ zero, zero ^ one, !one]), Cost::default());
+    assert_eq!(measure(|| vec![a ^ one]), Cost { not: 1, ..Cost::default() });
+    assert!(!(a & zero).value() && (a & one).value());
+}
+
+#[test]
+fn test_gfmul_costs() {
+    let bit_serial = gfmul_cost(CostStrategy::BitSerial, X_1, H);
+    let karatsuba = gfmul_cost(CostStrategy::Karatsuba, X_1, H);
+    assert_eq!(bit_serial.and, 128 * 128);
+    assert_eq!(bit_serial.depth, 1);
+    assert_eq!(karatsuba.and, 3 * 64 * 64);
+    assert_eq!(karatsuba.depth, 1);
+    assert!(karatsuba.xor < bit_serial.xor);
+
+    // the costs do not depend on the values of secret inputs
+    assert_eq!(gfmul_cost(CostStrategy::BitSerial, [0; 16], [0; 16]), bit_serial);
+    assert_eq!(
+        gfmul_cost(CostStrategy::Table4, [0; 16], H),
+        gfmul_cost(CostStrategy::Table4, X_2, H)
+    );
+}
+
+#[test]
+fn test_ghash_costs() {
+    let one = ghash_cost(CostStrategy::BitSerial, H, &[X_1]);
+    let two = ghash_cost(CostStrategy::BitSerial, H, &[X_1, X_2]);
+    assert_eq!(two.and, 2 * one.and);
+    assert_eq!(two.depth, 2);
+    assert_eq!(ghash_cost(CostStrategy::Karatsuba, H, &[]), Cost::default());
+}
+
+#[test]
+fn test_cost_report() {
+    let report = cost_report(H, 2);
+    assert_eq!(report.lines().count(), CostStrategy::ALL.len(), "\n{report}");
+    assert!(report.contains("BitSerial") && report.contains("AND  16384"));
+    assert!(report.contains("RecursiveKaratsuba") && report.contains("AND   2187"));
+
+    // aligned columns, each row labelled with its H assumption
+    let columns: Vec<usize> = report.lines().map(|line| line.find("gfmul:").unwrap()).collect();
+    assert!(columns.iter().all(|c| *c == columns[0]), "\n{report}");
+    for (line, strategy) in report.lines().zip(CostStrategy::ALL) {
+        let key = if strategy.public_h() { "public H" } else { "secret H" };
+        assert!(line.starts_with(&format!("{strategy:?}")) && line.contains(key), "{line}");
+    }
+    assert!(CostStrategy::Table8.public_h() && !CostStrategy::RecursiveKaratsuba.public_h());
+}
+
+#[test]
+fn test_recursive_karatsuba() {
+    let mut rng = StdRng::seed_from_u64(2);
+    // odd and uneven splits pad with constants
+    for n in [1, 2, 3, 5, 7, 64, 100, 128] {
+        let (a, b): (Vec<bool>, Vec<bool>) =
+            ((0..n).map(|_| rng.gen()).collect(), (0..n).map(|_| rng.gen()).collect());
+        for base in [1, 2, 3, 8] {
+            assert_eq!(
+                clmul_recursive_karatsuba(&a, &b, base),
+                clmul_bit_serial(&a, &b),
+                "n {n} base {base}"
+            );
+        }
+    }
+
+    let cost = gfmul_cost(CostStrategy::RecursiveKaratsuba, X_1, H);
+    assert_eq!(cost.and, 3usize.pow(7));
+    assert_eq!(cost.depth, 1);
+}
@@ -0,0 +1,23 @@
+//! Constant-time multiplication over $\text{GF}(2^{128})$.
+//!
+//! [`crate::gfmul`] branches on every bit of `b`, and `galois_reduce` branches on every bit of the
+//! upper half, so both leak the hash key through timing. [`ct_gfmul`] does the same shift-and-xor
+//! with a mask derived from each bit instead of a branch, as the portable fallback of
+//! [`crate::pclmul`] does, never indexes memory by secret data, and reduces with the branch-free
+//! shifts of `reduce_product`.
+
+use crate::{pclmul, reduce_product};
+
+#[cfg(test)] mod tests;
+
+/// Multiplication over the finite field $\text{GF}(2^{128})$ with no secret-dependent branches or
+/// indexing.
+pub fn ct_gfmul(a: [u8; 16], b: [u8; 16]) -> [u8; 16] {
+    pclmul::unreflect(ct_gfmul_uint(pclmul::reflect(a), pclmul::reflect(b)))
+}
+
+/// [`ct_gfmul`] on integers where bit `i` is the coefficient of $x^i$.
+pub(crate) fn ct_gfmul_uint(a: u128, b: u128) -> u128 {
+    let (upper128, lower128) = pclmul::soft_clmul128(a, b);
+    reduce_product(upper128, lower128)
+}
@@ -0,0 +1,102 @@
+use std::{hint::black_box, time::Instant};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+use crate::{backend::Backend, gfmul, parse_u128_as_array, pclmul, tests::*};
+
+#[test]
+fn test_ct_gfmul_vectors() {
+    for (a, b) in [(LONE, LONE), (RONE, RONE), (LC, RTHREE), (LC, RTHREE0), (H, X_1), (X_1, X_2)] {
+        assert_eq!(ct_gfmul(a, b), gfmul(a, b));
+    }
+    assert_eq!(ct_gfmul([0xff; 16], [0xff; 16]), gfmul([0xff; 16], [0xff; 16]));
+}
+
+#[test]
+fn test_ct_gfmul_basis() {
+    for i in (0..128).step_by(3) {
+        for j in 0..128 {
+            let (a, b) = (parse_u128_as_array(1 << i), parse_u128_as_array(1 << j));
+            assert_eq!(ct_gfmul(a, b), gfmul(a, b), "x^{i} * x^{j}");
+        }
+    }
+}
+
+#[test]
+fn test_ct_gfmul_random() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..1000 {
+        let (a, b): ([u8; 16], [u8; 16]) = (rng.gen(), rng.gen());
+        assert_eq!(ct_gfmul(a, b), gfmul(a, b));
+    }
+}
+
+/// Welch's t statistic between the timings of a fixed class (b = 0) and a random class of
+/// multiplicands, as in dudect. Each sample times a batch of calls, and samples above the 90th
+/// percentile are cropped as interrupt noise.
+fn dudect_t(backend: Backend, samples: usize) -> f64 {
+    const BATCH: usize = 16;
+    let mut rng = StdRng::seed_from_u64(1);
+    let a: [u8; 16] = rng.gen();
+    let (mut fixed, mut random) = (Vec::new(), Vec::new());
+
+    for _ in 0..samples {
+        let class_random = rng.gen::<bool>();
+        let bs: [[u8; 16]; BATCH] =
+            core::array::from_fn(|_| if class_random { rng.gen() } else { [0; 16] });
+
+        let start = Instant::now();
+        for b in bs {
+            black_box(backend.gfmul(black_box(a), black_box(b)));
+        }
+        let elapsed = start.elapsed().as_nanos() as f64;
+
+        if class_random {
+            random.push(elapsed)
+        } else {
+            fixed.push(elapsed)
+        }
+    }
+
+    let mut all: Vec<f64> = fixed.iter().chain(&random).copied().collect();
+    all.sort_by(f64::total_cmp);
+    let cutoff = all[all.len() * 9 / 10];
+    let crop = |v: Vec<f64>| v.into_iter().filter(|t| *t <= cutoff).collect::<Vec<_>>();
+    let (fixed, random) = (crop(fixed), crop(random));
+
+    let stats = |v: &[f64]| {
+        let n = v.len() as f64;
+        let mean = v.iter().sum::<f64>() / n;
+        let var = v.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        (mean, var, n)
+    };
+    let ((m0, v0, n0), (m1, v1, n1)) = (stats(&fixed), stats(&random));
+    (m0 - m1) / (v0 / n0 + v1 / n1).sqrt()
+}
+
+/// Times the portable path [`crate::AesGcm`] hashes with on CPUs without `pclmulqdq`. Timing is
+/// noisy on shared machines; run locally, one test at a time, with
+/// `cargo test -r -- --ignored --test-threads=1`.
+#[test]
+#[ignore]
+fn test_dudect_constant_time() {
+    let guard = pclmul::FallbackGuard::lock();
+    pclmul::force_fallback(true);
+    let t = dudect_t(Backend::Pclmulqdq, 200_000);
+    drop(guard);
+    // dudect treats |t| > 10 as a definite leak
+    assert!(t.abs() < 10.0, "data-dependent timing in the GHASH fallback: t = {t:.2}");
+}
+
+/// The harness must see the leak in the backends that branch on `b`, or a pass above means
+/// nothing; run locally as above.
+#[test]
+#[ignore]
+fn test_dudect_detects_leaks() {
+    let ts = Backend::ALL.map(|backend| (backend, dudect_t(backend, 100_000)));
+    for backend in [Backend::BitSerial, Backend::Karatsuba] {
+        let t = ts.iter().find(|(b, _)| *b == backend).unwrap().1;
+        assert!(t.abs() > 10.0, "{backend:?} shows no leak; t per backend: {ts:.2?}");
+    }
+}
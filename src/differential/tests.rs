@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use proptest::prelude::*;
+
+use super::*;
+use crate::tests::{H, X_1, X_2};
+
+fn fuzz_dir(kind: &str, target: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fuzz").join(kind).join(target)
+}
+
+#[test]
+fn test_split_input() {
+    assert_eq!(split_input(&[]), ([0; 16], &[][..]));
+    assert_eq!(split_input(&[1, 2]), ([1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0], &[][..]));
+    let data = [H, X_1].concat();
+    assert_eq!(split_input(&data), (H, &X_1[..]));
+}
+
+#[test]
+fn test_check() {
+    check(&[H, X_1, X_2].concat()).unwrap();
+    check(&[]).unwrap();
+    check(&H).unwrap();
+    check(&[&H[..], &X_1[..5]].concat()).unwrap();
+    check_gfmul(&[X_1, H].concat()).unwrap();
+    check_gfmul(&[7]).unwrap();
+}
+
+#[test]
+fn test_replay_corpus() {
+    for (target, check) in
+        [("differential", check as fn(&[u8]) -> Result<()>), ("gfmul", check_gfmul)]
+    {
+        assert!(
+            replay_dir(fuzz_dir("corpus", target), check).unwrap() > 0,
+            "{target}: empty seed corpus"
+        );
+        replay_dir(fuzz_dir("artifacts", target), check).unwrap();
+    }
+}
+
+#[test]
+fn test_replay_reports_file() {
+    let dir = std::env::temp_dir().join(format!("gfmul-replay-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("crash-0"), [1, 2, 3]).unwrap();
+
+    assert_eq!(replay_dir(&dir, check).unwrap(), 1);
+    let error = replay_dir(&dir, |_| anyhow::bail!("mismatch")).unwrap_err();
+    assert!(format!("{error:#}").contains("crash-0"));
+    assert_eq!(replay_dir(dir.join("missing"), check).unwrap(), 0);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig { cases: 64, failure_persistence: None, ..ProptestConfig::default() })]
+
+    #[test]
+    fn test_differential(data in prop::collection::vec(any::<u8>(), 0..100)) {
+        check(&data).map_err(|e| TestCaseError::fail(format!("{e:#}")))?;
+        check_gfmul(&data).map_err(|e| TestCaseError::fail(format!("{e:#}")))?;
+    }
+}
@@ -0,0 +1,116 @@
+//! A first-class element of $\text{GF}(2^{128})$ with operator overloading.
+//!
+//! [`Gf128`] wraps the same `u128` encoding that [`parse_array_as_uint`] produces: bit `i` of the
+//! integer is the coefficient of $x^i$. Addition and subtraction are xor, and multiplication
+//! reduces modulo $x^{128} + x^7 + x^2 + x + 1$ in constant time via [`crate::ct_gfmul`].
+//!
+//! e.g.
+//! ```
+//! use aes_proof_gfmul_prototype::Gf128;
+//!
+//! let (a, b, h) = (Gf128::from_uint(3), Gf128::from_uint(5), Gf128::from_uint(7));
+//! assert_eq!(a * h + b, Gf128::from_uint(9 ^ 5));
+//! ```
+
+use core::{
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use crate::{ct::ct_gfmul_uint, parse_array_as_uint, parse_u128_as_array};
+
+#[cfg(test)] mod tests;
+
+/// An element of $\text{GF}(2^{128})$, stored with bit `i` as the coefficient of $x^i$.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Gf128(u128);
+
+impl Gf128 {
+    /// The multiplicative identity, $x^0$.
+    pub const ONE: Self = Self(1);
+    /// The additive identity.
+    pub const ZERO: Self = Self(0);
+
+    /// Wrap an integer where bit `i` is the coefficient of $x^i$.
+    pub const fn from_uint(n: u128) -> Self { Self(n) }
+
+    /// Unwrap into an integer where bit `i` is the coefficient of $x^i$.
+    pub const fn to_uint(self) -> u128 { self.0 }
+
+    /// Parse a GHASH-convention byte array, e.g. a block or hash key as used by [`crate::ghash`].
+    ///
+    /// 1     = [ 0x80 0x00 ... 0x00 ]
+    /// x^127 = [ 0x00 ... 0x00 0x01 ]
+    pub fn from_ghash_bytes(bytes: [u8; 16]) -> Self { Self(parse_array_as_uint(bytes)) }
+
+    /// Serialize into a GHASH-convention byte array; the inverse of [`Gf128::from_ghash_bytes`].
+    pub fn to_ghash_bytes(self) -> [u8; 16] { parse_u128_as_array(self.0) }
+}
+
+impl Add for Gf128 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, rhs: Self) -> Self { Self(self.0 ^ rhs.0) }
+}
+
+impl Sub for Gf128 {
+    type Output = Self;
+
+    // characteristic 2: subtraction is addition
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self { Self(self.0 ^ rhs.0) }
+}
+
+impl Neg for Gf128 {
+    type Output = Self;
+
+    // characteristic 2: every element is its own additive inverse
+    fn neg(self) -> Self { self }
+}
+
+impl Mul for Gf128 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self { Self(ct_gfmul_uint(self.0, rhs.0)) }
+}
+
+impl AddAssign for Gf128 {
+    fn add_assign(&mut self, rhs: Self) { *self = *self + rhs; }
+}
+
+impl SubAssign for Gf128 {
+    fn sub_assign(&mut self, rhs: Self) { *self = *self - rhs; }
+}
+
+impl MulAssign for Gf128 {
+    fn mul_assign(&mut self, rhs: Self) { *self = *self * rhs; }
+}
+
+impl Sum for Gf128 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self { iter.fold(Self::ZERO, Add::add) }
+}
+
+impl<'a> Sum<&'a Gf128> for Gf128 {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self { iter.copied().sum() }
+}
+
+impl Product for Gf128 {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self { iter.fold(Self::ONE, Mul::mul) }
+}
+
+impl<'a> Product<&'a Gf128> for Gf128 {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self { iter.copied().product() }
+}
+
+impl ConstantTimeEq for Gf128 {
+    fn ct_eq(&self, other: &Self) -> Choice { self.0.ct_eq(&other.0) }
+}
+
+impl ConditionallySelectable for Gf128 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(u128::conditional_select(&a.0, &b.0, choice))
+    }
+}
@@ -0,0 +1,97 @@
+use ghash::{
+    universal_hash::{KeyInit, UniversalHash},
+    GHash,
+};
+use hex_literal::hex;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use super::*;
+use crate::{
+    gfmul, ghash,
+    tests::{H, X_1, X_2},
+};
+
+#[test]
+fn test_ghash_bytes_roundtrip() {
+    assert_eq!(Gf128::from_ghash_bytes(hex!("80000000000000000000000000000000")), Gf128::ONE);
+    assert_eq!(Gf128::from_ghash_bytes([0; 16]), Gf128::ZERO);
+    assert_eq!(
+        Gf128::from_ghash_bytes(hex!("00000000000000000000000000000001")).to_uint(),
+        1 << 127
+    );
+    assert_eq!(Gf128::from_ghash_bytes(H).to_ghash_bytes(), H);
+    assert_eq!(Gf128::from_uint(X_1[0] as u128).to_uint(), X_1[0] as u128);
+}
+
+#[test]
+fn test_add_sub_neg() {
+    let (a, b) = (Gf128::from_ghash_bytes(X_1), Gf128::from_ghash_bytes(X_2));
+    assert_eq!(a + b, Gf128::from_uint(a.to_uint() ^ b.to_uint()));
+    assert_eq!(a - b, a + b);
+    assert_eq!(-a, a);
+    assert_eq!(a + a, Gf128::ZERO);
+    assert_eq!(a + Gf128::ZERO, a);
+
+    let mut c = a;
+    c += b;
+    assert_eq!(c, a + b);
+    c -= b;
+    assert_eq!(c, a);
+}
+
+#[test]
+fn test_mul() {
+    let (a, h) = (Gf128::from_ghash_bytes(X_1), Gf128::from_ghash_bytes(H));
+    assert_eq!(a * h, Gf128::from_ghash_bytes(gfmul(X_1, H)));
+    assert_eq!(a * Gf128::ONE, a);
+    assert_eq!(a * Gf128::ZERO, Gf128::ZERO);
+    // x^127 * x = x^128 = x^7 + x^2 + x + 1
+    assert_eq!(Gf128::from_uint(1 << 127) * Gf128::from_uint(2), Gf128::from_uint(135));
+
+    let mut c = a;
+    c *= h;
+    assert_eq!(c, a * h);
+}
+
+#[test]
+fn test_sum_product() {
+    let xs = [X_1, X_2, H].map(Gf128::from_ghash_bytes);
+    assert_eq!(xs.iter().sum::<Gf128>(), xs[0] + xs[1] + xs[2]);
+    assert_eq!(xs.into_iter().sum::<Gf128>(), xs[0] + xs[1] + xs[2]);
+    assert_eq!(xs.iter().product::<Gf128>(), xs[0] * xs[1] * xs[2]);
+    assert_eq!(xs.into_iter().product::<Gf128>(), xs[0] * xs[1] * xs[2]);
+    assert_eq!(core::iter::empty::<Gf128>().sum::<Gf128>(), Gf128::ZERO);
+    assert_eq!(core::iter::empty::<Gf128>().product::<Gf128>(), Gf128::ONE);
+}
+
+#[test]
+fn test_horner_matches_ghash() {
+    // ghash(h, [x1, x2]) = (x1 * h + x2) * h
+    let h = Gf128::from_ghash_bytes(H);
+    let (x1, x2) = (Gf128::from_ghash_bytes(X_1), Gf128::from_ghash_bytes(X_2));
+    let expected = ghash(H, &[X_1, X_2]);
+    assert_eq!(((x1 * h + x2) * h).to_ghash_bytes(), expected);
+
+    let mut ghash_rc = GHash::new(&H.into());
+    ghash_rc.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(ghash_rc.finalize().as_slice(), expected);
+}
+
+#[test]
+fn test_constant_time_traits() {
+    let (a, b) = (Gf128::from_ghash_bytes(X_1), Gf128::from_ghash_bytes(X_2));
+    assert!(bool::from(a.ct_eq(&a)));
+    assert!(!bool::from(a.ct_eq(&b)));
+    assert_eq!(Gf128::conditional_select(&a, &b, Choice::from(0)), a);
+    assert_eq!(Gf128::conditional_select(&a, &b, Choice::from(1)), b);
+
+    let mut c = a;
+    c.conditional_assign(&b, Choice::from(1));
+    assert_eq!(c, b);
+
+    let (mut x, mut y) = (a, b);
+    Gf128::conditional_swap(&mut x, &mut y, Choice::from(0));
+    assert_eq!((x, y), (a, b));
+    Gf128::conditional_swap(&mut x, &mut y, Choice::from(1));
+    assert_eq!((x, y), (b, a));
+}
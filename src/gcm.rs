@@ -0,0 +1,114 @@
+//! AES-GCM (SP 800-38D) built on the crate's own AES and GHASH.
+//!
+//! Every step is spelled out so it can be mirrored in circuits:
+//!
+//! H  = AES_K(0^128)
+//! J0 = IV || 0^31 || 1                                   ; 96-bit IVs
+//! J0 = GHASH_H(IV || 0^(s+64) || [len(IV)]_64)           ; any other IV length
+//! C  = GCTR_K(inc32(J0), P)
+//! T  = AES_K(J0) ^ GHASH_H(A || 0^v || C || 0^u || [len(A)]_64 || [len(C)]_64)
+//!
+//! For testing and circuit work only: the software AES looks up its S-box by secret bytes and
+//! branches in `xtime`, so the key, and with it H, leaks through timing whatever GHASH backend is
+//! used.
+
+use std::fmt;
+
+use anyhow::{bail, ensure, Result};
+use subtle::ConstantTimeEq;
+
+use crate::{aes::Aes, hasher::ghash_gcm, Ghash};
+
+#[cfg(test)] mod tests;
+
+/// An AES-128/192/256-GCM key with its expanded schedule and hash key. Not constant-time; see the
+/// module docs. `Debug` redacts the round keys and H.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AesGcm {
+    aes: Aes,
+    h:   [u8; 16],
+}
+
+impl fmt::Debug for AesGcm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AesGcm").field("aes", &self.aes).finish_non_exhaustive()
+    }
+}
+
+impl AesGcm {
+    /// Expand a 16, 24 or 32 byte key and derive H = AES_K(0^128).
+    pub fn new(key: &[u8]) -> Result<Self> {
+        let aes = Aes::new(key)?;
+        let h = aes.encrypt_block([0; 16]);
+        Ok(Self { aes, h })
+    }
+
+    /// The GHASH key H = AES_K(0^128).
+    pub fn hash_key(&self) -> [u8; 16] { self.h }
+
+    /// The pre-counter block J0 for a non-empty `iv`.
+    pub fn j0(&self, iv: &[u8]) -> Result<[u8; 16]> {
+        ensure!(!iv.is_empty(), "GCM IV must be at least one byte");
+
+        if iv.len() == 12 {
+            let mut j0 = [0; 16];
+            j0[..12].copy_from_slice(iv);
+            j0[15] = 1;
+            return Ok(j0);
+        }
+
+        let mut hasher = Ghash::new(self.h);
+        hasher.update_padded(iv);
+        hasher.update(&[0; 8]);
+        hasher.update(&(iv.len() as u64 * 8).to_be_bytes());
+        Ok(hasher.finalize())
+    }
+
+    /// Encrypt `plaintext` and authenticate it with `aad`, returning (ciphertext, tag).
+    pub fn seal(&self, iv: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(Vec<u8>, [u8; 16])> {
+        let j0 = self.j0(iv)?;
+        let ciphertext = self.gctr(inc32(j0), plaintext);
+        let tag = self.tag(j0, aad, &ciphertext);
+        Ok((ciphertext, tag))
+    }
+
+    /// Verify `tag` over `aad` and `ciphertext`, and decrypt; errors if the tag does not match.
+    ///
+    /// `tag` may be truncated to any length SP 800-38D allows, 4, 8 or 12 to 16 bytes, and is
+    /// compared with the leading bytes of the full tag.
+    pub fn open(&self, iv: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8]) -> Result<Vec<u8>> {
+        ensure!(matches!(tag.len(), 4 | 8 | 12..=16), "unsupported GCM tag length {}", tag.len());
+        let j0 = self.j0(iv)?;
+        let expected = self.tag(j0, aad, ciphertext);
+
+        if !bool::from(expected[..tag.len()].ct_eq(tag)) {
+            bail!("GCM tag mismatch");
+        }
+        Ok(self.gctr(inc32(j0), ciphertext))
+    }
+
+    /// T = AES_K(J0) ^ S
+    fn tag(&self, j0: [u8; 16], aad: &[u8], ciphertext: &[u8]) -> [u8; 16] {
+        let s = ghash_gcm(self.h, aad, ciphertext);
+        let ek_j0 = self.aes.encrypt_block(j0);
+        core::array::from_fn(|i| ek_j0[i] ^ s[i])
+    }
+
+    /// GCTR_K(cb, x): xor `x` with the keystream AES_K(cb), AES_K(inc32(cb)), ...
+    fn gctr(&self, mut cb: [u8; 16], x: &[u8]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(x.len());
+        for chunk in x.chunks(16) {
+            let keystream = self.aes.encrypt_block(cb);
+            out.extend(chunk.iter().zip(keystream).map(|(a, b)| a ^ b));
+            cb = inc32(cb);
+        }
+        out
+    }
+}
+
+/// Increment the rightmost 32 bits of `block` as a big-endian integer, mod 2^32.
+pub fn inc32(mut block: [u8; 16]) -> [u8; 16] {
+    let ctr = u32::from_be_bytes(block[12..].try_into().unwrap()).wrapping_add(1);
+    block[12..].copy_from_slice(&ctr.to_be_bytes());
+    block
+}
@@ -0,0 +1,149 @@
+use aes_gcm::{
+    aead::{
+        consts::{U1, U12, U16, U64},
+        generic_array::ArrayLength,
+        AeadInPlace, KeyInit,
+    },
+    aes::{
+        cipher::{BlockCipher, BlockEncrypt, BlockSizeUser},
+        Aes128, Aes192, Aes256,
+    },
+    AesGcm as RcAesGcm,
+};
+use hex_literal::hex;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+
+/// seal with RustCrypto, for a given cipher and nonce size
+fn rustcrypto_seal<C, N>(key: &[u8], iv: &[u8], aad: &[u8], pt: &[u8]) -> (Vec<u8>, [u8; 16])
+where
+    C: BlockCipher + BlockSizeUser<BlockSize = U16> + BlockEncrypt + KeyInit,
+    N: ArrayLength<u8>, {
+    let cipher = RcAesGcm::<C, N>::new_from_slice(key).unwrap();
+    let mut buf = pt.to_vec();
+    let tag = cipher.encrypt_in_place_detached(iv.into(), aad, &mut buf).unwrap();
+    (buf, tag.into())
+}
+
+#[test]
+fn test_inc32() {
+    assert_eq!(inc32([0; 16]), hex!("00000000000000000000000000000001"));
+    assert_eq!(
+        inc32(hex!("ffffffffffffffffffffffffffffffff")),
+        hex!("ffffffffffffffffffffffff00000000")
+    );
+    assert_eq!(
+        inc32(hex!("000000000000000000000000000000ff")),
+        hex!("00000000000000000000000000000100")
+    );
+}
+
+#[test]
+fn test_debug_redacts_key() {
+    for key in [&[0x5a; 16][..], &[0x5a; 24], &[0x5a; 32]] {
+        let gcm = AesGcm::new(key).unwrap();
+        let rounds = key.len() / 4 + 6;
+        assert_eq!(
+            format!("{gcm:?}"),
+            format!("AesGcm {{ aes: Aes {{ rounds: {rounds}, .. }}, .. }}")
+        );
+    }
+}
+
+#[test]
+fn test_empty_iv() {
+    let gcm = AesGcm::new(&[0; 16]).unwrap();
+    assert!(gcm.seal(&[], &[], &[]).is_err());
+    assert!(AesGcm::new(&[0; 17]).is_err());
+}
+
+#[test]
+fn test_mcgrew_viega() {
+    // test case 2
+    let gcm = AesGcm::new(&[0; 16]).unwrap();
+    assert_eq!(gcm.hash_key(), hex!("66e94bd4ef8a2c3b884cfa59ca342b2e"));
+    let (ct, tag) = gcm.seal(&[0; 12], &[], &[0; 16]).unwrap();
+    assert_eq!(ct, hex!("0388dace60b6a392f328c2b971b2fe78"));
+    assert_eq!(tag, hex!("ab6e47d42cec13bdf53a67b21257bddf"));
+
+    // test case 6: 60-byte iv, hashed through GHASH into J0
+    let gcm = AesGcm::new(&hex!("feffe9928665731c6d6a8f9467308308")).unwrap();
+    let iv = hex!(
+        "9313225df88406e555909c5aff5269aa6a7a9538534f7da1e4c303d2a318a728"
+        "c3c0c95156809539fcf0e2429a6b525416aedbf5a0de6a57a637b39b"
+    );
+    let pt = hex!(
+        "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72"
+        "1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39"
+    );
+    let aad = hex!("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+    let (ct, tag) = gcm.seal(&iv, &aad, &pt).unwrap();
+    assert_eq!(
+        ct,
+        hex!(
+            "8ce24998625615b603a033aca13fb894be9112a5c3a211a8ba262a3cca7e2ca7"
+            "01e4a9a4fba43c90ccdcb281d48c7c6fd62875d2aca417034c34aee5"
+        )
+    );
+    assert_eq!(tag, hex!("619cc5aefffe0bfa462af43c1699d050"));
+    assert_eq!(gcm.open(&iv, &aad, &ct, &tag).unwrap(), pt);
+}
+
+#[test]
+fn test_open_rejects_modified() {
+    let gcm = AesGcm::new(&[7; 32]).unwrap();
+    let (ct, tag) = gcm.seal(&[1; 12], b"aad", b"some plaintext").unwrap();
+    assert_eq!(gcm.open(&[1; 12], b"aad", &ct, &tag).unwrap(), b"some plaintext");
+
+    let mut bad_tag = tag;
+    bad_tag[15] ^= 1;
+    assert!(gcm.open(&[1; 12], b"aad", &ct, &bad_tag).is_err());
+    let mut bad_ct = ct.clone();
+    bad_ct[0] ^= 1;
+    assert!(gcm.open(&[1; 12], b"aad", &bad_ct, &tag).is_err());
+    assert!(gcm.open(&[1; 12], b"aaD", &ct, &tag).is_err());
+    assert!(gcm.open(&[2; 12], b"aad", &ct, &tag).is_err());
+}
+
+#[test]
+fn test_matches_rustcrypto() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for (aad_len, pt_len) in [(0, 0), (0, 1), (13, 0), (13, 17), (16, 32), (20, 60), (3, 100)] {
+        let key: [u8; 32] = rng.gen();
+        let aad: Vec<u8> = (0..aad_len).map(|_| rng.gen()).collect();
+        let pt: Vec<u8> = (0..pt_len).map(|_| rng.gen()).collect();
+
+        let iv: [u8; 12] = rng.gen();
+        let expected = rustcrypto_seal::<Aes128, U12>(&key[..16], &iv, &aad, &pt);
+        assert_eq!(AesGcm::new(&key[..16]).unwrap().seal(&iv, &aad, &pt).unwrap(), expected);
+        let expected = rustcrypto_seal::<Aes192, U12>(&key[..24], &iv, &aad, &pt);
+        assert_eq!(AesGcm::new(&key[..24]).unwrap().seal(&iv, &aad, &pt).unwrap(), expected);
+        let expected = rustcrypto_seal::<Aes256, U12>(&key, &iv, &aad, &pt);
+        assert_eq!(AesGcm::new(&key).unwrap().seal(&iv, &aad, &pt).unwrap(), expected);
+
+        // non-96-bit ivs
+        let iv: [u8; 64] = core::array::from_fn(|_| rng.gen());
+        let expected = rustcrypto_seal::<Aes256, U64>(&key, &iv, &aad, &pt);
+        assert_eq!(AesGcm::new(&key).unwrap().seal(&iv, &aad, &pt).unwrap(), expected);
+        let expected = rustcrypto_seal::<Aes128, U16>(&key[..16], &iv[..16], &aad, &pt);
+        assert_eq!(AesGcm::new(&key[..16]).unwrap().seal(&iv[..16], &aad, &pt).unwrap(), expected);
+        let expected = rustcrypto_seal::<Aes128, U1>(&key[..16], &iv[..1], &aad, &pt);
+        assert_eq!(AesGcm::new(&key[..16]).unwrap().seal(&iv[..1], &aad, &pt).unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_open_truncated_tag() {
+    let gcm = AesGcm::new(&[3; 16]).unwrap();
+    let (ct, tag) = gcm.seal(&[1; 12], b"aad", b"some plaintext").unwrap();
+    for len in [4, 8, 12, 13, 15] {
+        assert_eq!(gcm.open(&[1; 12], b"aad", &ct, &tag[..len]).unwrap(), b"some plaintext");
+        let mut bad_tag = tag[..len].to_vec();
+        bad_tag[len - 1] ^= 1;
+        assert!(gcm.open(&[1; 12], b"aad", &ct, &bad_tag).is_err());
+    }
+    for len in [0, 1, 7, 11, 17] {
+        assert!(gcm.open(&[1; 12], b"aad", &ct, &[0; 17][..len]).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+//! A streaming GHASH hasher over byte input.
+//!
+//! [`crate::ghash`] needs the whole message up front as whole blocks. [`Ghash`] instead accepts
+//! bytes in arbitrary chunks, buffers a partial block internally, and zero-pads it at a block
+//! boundary as SP 800-38D requires: on [`Ghash::update_padded`] and on [`Ghash::finalize`].
+//!
+//! [`ghash_gcm`] applies the full GCM framing on top: padded AAD, padded ciphertext, and the
+//! `len(A) || len(C)` block.
+
+use crate::backend::Backend;
+
+#[cfg(test)] mod tests;
+
+/// Incremental GHASH under a fixed hash key.
+#[derive(Clone, Debug)]
+pub struct Ghash {
+    hashkey:  [u8; 16],
+    backend:  Backend,
+    /// byte-reverse each block before absorbing it, to serve POLYVAL; see [`crate::Polyval`]
+    reversed: bool,
+    /// running hash over every complete block so far
+    y:        [u8; 16],
+    /// bytes of the current partial block; only the first `buffered` are meaningful
+    buffer:   [u8; 16],
+    buffered: usize,
+}
+
+impl Ghash {
+    /// A hasher under the GHASH-convention hash key `h`, multiplying with [`Backend::Pclmulqdq`].
+    pub fn new(h: [u8; 16]) -> Self { Self::with_backend(h, Backend::Pclmulqdq) }
+
+    /// A hasher under the GHASH-convention hash key `h`, multiplying with `backend`.
+    pub fn with_backend(h: [u8; 16], backend: Backend) -> Self {
+        Self { hashkey: h, backend, reversed: false, y: [0; 16], buffer: [0; 16], buffered: 0 }
+    }
+
+    /// A hasher that byte-reverses every block before absorbing it.
+    pub(crate) fn reversed(h: [u8; 16]) -> Self { Self { reversed: true, ..Self::new(h) } }
+
+    /// Absorb `data` as a continuation of the message; a trailing partial block stays buffered.
+    pub fn update(&mut self, mut data: &[u8]) {
+        // top up a partial block first
+        if self.buffered > 0 {
+            let take = data.len().min(16 - self.buffered);
+            self.buffer[self.buffered..self.buffered + take].copy_from_slice(&data[..take]);
+            self.buffered += take;
+            data = &data[take..];
+            if self.buffered < 16 {
+                return;
+            }
+            self.absorb(self.buffer);
+            self.buffered = 0;
+        }
+
+        let mut blocks = data.chunks_exact(16);
+        for block in &mut blocks {
+            self.absorb(block.try_into().unwrap());
+        }
+
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+    }
+
+    /// Absorb `data`, then zero-pad to the next block boundary, as for the AAD and ciphertext
+    /// sections of GCM.
+    pub fn update_padded(&mut self, data: &[u8]) {
+        self.update(data);
+        self.pad();
+    }
+
+    /// The hash of everything absorbed so far, zero-padding a buffered partial block.
+    pub fn finalize(&self) -> [u8; 16] {
+        let mut this = self.clone();
+        this.pad();
+        this.y
+    }
+
+    /// Forget all input, keeping the hash key and backend.
+    pub fn reset(&mut self) {
+        self.y = [0; 16];
+        self.buffered = 0;
+    }
+
+    /// Zero-pad and absorb a buffered partial block, if any.
+    fn pad(&mut self) {
+        if self.buffered > 0 {
+            self.buffer[self.buffered..].fill(0);
+            self.absorb(self.buffer);
+            self.buffered = 0;
+        }
+    }
+
+    fn absorb(&mut self, mut block: [u8; 16]) {
+        if self.reversed {
+            block.reverse();
+        }
+        (0..16).for_each(|i| self.y[i] ^= block[i]);
+        self.y = self.backend.gfmul(self.y, self.hashkey);
+    }
+}
+
+/// The GCM hash S = GHASH_H(A || 0^v || C || 0^u || [len(A)]_64 || [len(C)]_64) of SP 800-38D,
+/// i.e. the value that AES-GCM encrypts into the tag.
+///
+/// Each section is zero-padded to a block boundary separately, and the lengths are in bits,
+/// big-endian.
+pub fn ghash_gcm(h: [u8; 16], aad: &[u8], ciphertext: &[u8]) -> [u8; 16] {
+    let mut hasher = Ghash::new(h);
+    hasher.update_padded(aad);
+    hasher.update_padded(ciphertext);
+    hasher.update(&length_block(aad.len(), ciphertext.len()));
+    hasher.finalize()
+}
+
+/// [len(A)]_64 || [len(C)]_64, with lengths in bits
+fn length_block(aad_len: usize, ciphertext_len: usize) -> [u8; 16] {
+    let mut block = [0; 16];
+    block[..8].copy_from_slice(&(aad_len as u64 * 8).to_be_bytes());
+    block[8..].copy_from_slice(&(ciphertext_len as u64 * 8).to_be_bytes());
+    block
+}
@@ -0,0 +1,141 @@
+use ghash::{
+    universal_hash::{KeyInit, UniversalHash},
+    GHash,
+};
+use hex_literal::hex;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+use crate::{ghash, tests::*};
+
+/// zero-pad `data` into whole blocks
+fn padded_blocks(data: &[u8]) -> Vec<[u8; 16]> {
+    data.chunks(16)
+        .map(|chunk| {
+            let mut block = [0; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            block
+        })
+        .collect()
+}
+
+#[test]
+fn test_empty() {
+    assert_eq!(Ghash::new(H).finalize(), [0; 16]);
+    let mut hasher = Ghash::new(H);
+    hasher.update(&[]);
+    hasher.update_padded(&[]);
+    assert_eq!(hasher.finalize(), [0; 16]);
+}
+
+#[test]
+fn test_whole_blocks() {
+    let mut hasher = Ghash::new(H);
+    hasher.update(&X_1);
+    assert_eq!(hasher.finalize(), ghash(H, &[X_1]));
+    hasher.update(&X_2);
+    assert_eq!(hasher.finalize(), ghash(H, &[X_1, X_2]));
+}
+
+#[test]
+fn test_arbitrary_chunks() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for len in [0, 1, 15, 16, 17, 31, 32, 33, 100, 257] {
+        let data: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+        let expected = ghash(H, &padded_blocks(&data));
+
+        for backend in Backend::ALL {
+            let mut hasher = Ghash::with_backend(H, backend);
+            let mut rest = &data[..];
+            while !rest.is_empty() {
+                let (chunk, tail) = rest.split_at(rng.gen_range(0..=rest.len().min(40)));
+                hasher.update(chunk);
+                rest = tail;
+            }
+            assert_eq!(hasher.finalize(), expected, "len={len}, {backend:?}");
+        }
+    }
+}
+
+#[test]
+fn test_update_padded_matches_rustcrypto() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for (aad_len, ct_len) in [(0, 0), (0, 5), (13, 0), (13, 17), (16, 32), (20, 60)] {
+        let aad: Vec<u8> = (0..aad_len).map(|_| rng.gen()).collect();
+        let ct: Vec<u8> = (0..ct_len).map(|_| rng.gen()).collect();
+
+        let mut ghash_rc = GHash::new(&H.into());
+        ghash_rc.update_padded(&aad);
+        ghash_rc.update_padded(&ct);
+
+        // split the aad across calls; padding only happens at the section boundary
+        let mut hasher = Ghash::new(H);
+        hasher.update(&aad[..aad_len / 2]);
+        hasher.update_padded(&aad[aad_len / 2..]);
+        hasher.update_padded(&ct);
+
+        assert_eq!(ghash_rc.finalize().as_slice(), hasher.finalize());
+    }
+}
+
+#[test]
+fn test_reset() {
+    let mut hasher = Ghash::new(H);
+    hasher.update(&[1, 2, 3]);
+    hasher.update(&X_1);
+    hasher.reset();
+    assert_eq!(hasher.finalize(), [0; 16]);
+    hasher.update(&X_2);
+    assert_eq!(hasher.finalize(), ghash(H, &[X_2]));
+}
+
+#[test]
+fn test_finalize_does_not_consume_buffer() {
+    let mut hasher = Ghash::new(H);
+    hasher.update(&X_1[..7]);
+    let _ = hasher.finalize();
+    hasher.update(&X_1[7..]);
+    assert_eq!(hasher.finalize(), ghash(H, &[X_1]));
+}
+
+#[test]
+fn test_length_block() {
+    assert_eq!(length_block(0, 0), [0; 16]);
+    assert_eq!(length_block(1, 2), hex!("00000000000000080000000000000010"));
+    assert_eq!(length_block(20, 60), hex!("00000000000000a000000000000001e0"));
+}
+
+#[test]
+fn test_ghash_gcm_mcgrew_viega() {
+    // "The Galois/Counter Mode of Operation (GCM)", McGrew & Viega, test cases 1 and 2
+    let h = hex!("66e94bd4ef8a2c3b884cfa59ca342b2e");
+    assert_eq!(ghash_gcm(h, &[], &[]), [0; 16]);
+    let c = hex!("0388dace60b6a392f328c2b971b2fe78");
+    assert_eq!(ghash_gcm(h, &[], &c), hex!("f38cbb1ad69223dcc3457ae5b6b0f885"));
+
+    // test case 4: 20 bytes of aad, 60 bytes of ciphertext
+    let h = hex!("b83b533708bf535d0aa6e52980d53b78");
+    let a = hex!("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+    let c = hex!(
+        "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e"
+        "21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091"
+    );
+    assert_eq!(ghash_gcm(h, &a, &c), hex!("698e57f70e6ecc7fd9463b7260a9ae5f"));
+}
+
+#[test]
+fn test_ghash_gcm_matches_rustcrypto() {
+    let mut rng = StdRng::seed_from_u64(2);
+    for (aad_len, ct_len) in [(0, 0), (0, 5), (13, 0), (13, 17), (16, 32), (20, 60), (100, 3)] {
+        let h: [u8; 16] = rng.gen();
+        let aad: Vec<u8> = (0..aad_len).map(|_| rng.gen()).collect();
+        let ct: Vec<u8> = (0..ct_len).map(|_| rng.gen()).collect();
+
+        let mut ghash_rc = GHash::new(&h.into());
+        ghash_rc.update_padded(&aad);
+        ghash_rc.update_padded(&ct);
+        ghash_rc.update(&[length_block(aad_len, ct_len).into()]);
+
+        assert_eq!(ghash_rc.finalize().as_slice(), ghash_gcm(h, &aad, &ct));
+    }
+}
@@ -0,0 +1,44 @@
+//! Quotient/remainder hints, so circuits verify the reduction instead of computing it.
+//!
+//! For the unreduced 256-bit product `upper128 * x^128 + lower128` of gfmul, the hint is the pair
+//! (q, r) with
+//!
+//! upper128 * x^128 + lower128 = q·P + r  over GF(2)[x], deg r < 128
+//!
+//! where P = x^128 + x^7 + x^2 + x + 1. A circuit given (q, r) only checks one carryless
+//! multiplication q·P and an xor, see [`verify_reduction_hint`], rather than running
+//! `galois_reduce`. The remainder r is the field product.
+
+use crate::{karatsuba::clmul128, reduce_product};
+
+#[cfg(test)] mod tests;
+
+/// P - x^128 = x^7 + x^2 + x + 1
+const POLY_LOW: u128 = 135;
+
+/// The quotient and remainder of an unreduced product by P = x^128 + x^7 + x^2 + x + 1.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ReductionHint {
+    /// q, of degree at most 127
+    pub quotient:  u128,
+    /// r, of degree at most 127; the reduced field element
+    pub remainder: u128,
+}
+
+/// Divide `upper128 * x^128 + lower128` by P.
+///
+/// With x^128 = P + (x^7 + x^2 + x + 1), upper·x^128 = upper·P + upper·(x^7 + x^2 + x + 1); the
+/// second term spills at most 7 bits past x^127, which are divided once more. So
+/// q = upper + spill, and r is the shift-and-xor reduction of `reduce_product`.
+pub fn reduction_hint(upper128: u128, lower128: u128) -> ReductionHint {
+    let spill = (upper128 >> 127) ^ (upper128 >> 126) ^ (upper128 >> 121);
+    ReductionHint { quotient: upper128 ^ spill, remainder: reduce_product(upper128, lower128) }
+}
+
+/// Check upper128·x^128 + lower128 = q·P + r, with q·P = q·x^128 + q·(x^7 + x^2 + x + 1).
+///
+/// Both q and r are 128-bit, so their degrees are bounded by construction.
+pub fn verify_reduction_hint(upper128: u128, lower128: u128, hint: &ReductionHint) -> bool {
+    let (hi, lo) = clmul128(hint.quotient, POLY_LOW);
+    (hint.quotient ^ hi, lo ^ hint.remainder) == (upper128, lower128)
+}
@@ -0,0 +1,75 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+use crate::{galois_reduce, gfmul, parse_array_as_uint, parse_u128_as_array, tests::*};
+
+/// schoolbook polynomial long division by P, one bit at a time
+fn long_division(mut upper: u128, mut lower: u128) -> ReductionHint {
+    let mut quotient = 0;
+    for k in (128..256).rev() {
+        if (upper >> (k - 128)) & 1 == 1 {
+            let shift = k - 128;
+            quotient |= 1 << shift;
+            // subtract P·x^shift = x^k + (x^7 + x^2 + x + 1)·x^shift
+            upper ^= 1 << shift;
+            let (hi, lo) = clmul128(POLY_LOW, 1 << shift);
+            upper ^= hi;
+            lower ^= lo;
+        }
+    }
+    assert_eq!(upper, 0);
+    ReductionHint { quotient, remainder: lower }
+}
+
+#[test]
+fn test_galois_reduce_edge_cases() {
+    // the cases of test_galois_reduce, as the upper half with nothing below
+    for upper in [0, 1, 2, 3, 4, 5, 1u128 << 120, 1u128 << 121, (1u128 << 121) + (1u128 << 120)] {
+        let hint = reduction_hint(upper, 0);
+        assert_eq!(hint.remainder, galois_reduce(upper));
+        assert_eq!(hint, long_division(upper, 0));
+        assert!(verify_reduction_hint(upper, 0, &hint));
+    }
+    // x^128 = 1·P + (x^7 + x^2 + x + 1)
+    assert_eq!(reduction_hint(1, 0), ReductionHint { quotient: 1, remainder: 135 });
+    // x^249 = x^121·x^128: the first power whose reduction spills past x^127
+    assert_eq!(reduction_hint(1 << 121, 0).quotient, (1 << 121) | 1);
+}
+
+#[test]
+fn test_extremes() {
+    for (upper, lower) in
+        [(0, 0), (0, u128::MAX), (u128::MAX, 0), (u128::MAX, u128::MAX), (1 << 127, 1)]
+    {
+        let hint = reduction_hint(upper, lower);
+        assert_eq!(hint, long_division(upper, lower));
+        assert!(verify_reduction_hint(upper, lower, &hint));
+    }
+}
+
+#[test]
+fn test_random_products() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..500 {
+        let (a, b): ([u8; 16], [u8; 16]) = (rng.gen(), rng.gen());
+        let (upper, lower) = clmul128(parse_array_as_uint(a), parse_array_as_uint(b));
+        let hint = reduction_hint(upper, lower);
+        assert_eq!(hint, long_division(upper, lower));
+        assert_eq!(parse_u128_as_array(hint.remainder), gfmul(a, b));
+        assert!(verify_reduction_hint(upper, lower, &hint));
+    }
+}
+
+#[test]
+fn test_rejects_bad_hints() {
+    let (upper, lower) = clmul128(parse_array_as_uint(H), parse_array_as_uint(X_1));
+    let hint = reduction_hint(upper, lower);
+    for bit in [0, 1, 7, 64, 127] {
+        let bad = ReductionHint { quotient: hint.quotient ^ (1 << bit), ..hint };
+        assert!(!verify_reduction_hint(upper, lower, &bad));
+        let bad = ReductionHint { remainder: hint.remainder ^ (1 << bit), ..hint };
+        assert!(!verify_reduction_hint(upper, lower, &bad));
+    }
+    // the all-zero hint fails for a nonzero product
+    assert!(!verify_reduction_hint(upper, lower, &ReductionHint::default()));
+}
@@ -0,0 +1,51 @@
+//! Karatsuba multiplication over $\text{GF}(2^{128})$.
+//!
+//! This is the sound version of [`crate::_sadmode_gfmul`]: operands are split into 64-bit halves
+//! with `parse_array_as_pair`, and the three half-products `rr`, `lr`, `ll` are computed with a
+//! true carryless 64×64→128 multiplication, so no carries bleed between bit positions.
+
+use crate::{galois_reduce, parse_array_as_uint, parse_u128_as_array};
+
+#[cfg(test)] mod tests;
+
+/// Carryless multiplication of two 64-bit polynomials over GF(2), returning the 127-bit product.
+///
+/// Bit `i` of each operand is the coefficient of $x^i$.
+///
+/// e.g.
+/// clmul64(3, 3) = (1 + x)(1 + x) = 1 + x^2 ; return 5
+pub fn clmul64(a: u64, b: u64) -> u128 {
+    (0..64).fold(0, |acc, i| if (b >> i) & 1 == 1 { acc ^ ((a as u128) << i) } else { acc })
+}
+
+/// Multiplication over the finite field $\text{GF}(2^{128})$ with a single level of Karatsuba.
+pub fn karatsuba_gfmul(a: [u8; 16], b: [u8; 16]) -> [u8; 16] {
+    let (upper, lower) = clmul128(parse_array_as_uint(a), parse_array_as_uint(b));
+
+    // reduce the upper 128 bits back into the field
+    parse_u128_as_array(lower ^ galois_reduce(upper))
+}
+
+/// Carryless 128×128→256 multiplication with a single level of Karatsuba, returning the unreduced
+/// product as (upper128, lower128).
+///
+/// With a = al·x^64 + ar and b = bl·x^64 + br, split as by `parse_array_as_pair`:
+///
+/// rr = ar·br                   ; bits 0..128
+/// lr = (al+ar)(bl+br) - ll - rr ; bits 64..192
+/// ll = al·bl                   ; bits 128..256
+pub fn clmul128(a: u128, b: u128) -> (u128, u128) {
+    let (al, ar) = ((a >> 64) as u64, a as u64);
+    let (bl, br) = ((b >> 64) as u64, b as u64);
+
+    // bits 0..128
+    let rr = clmul64(ar, br);
+    // bits 128..256
+    let ll = clmul64(al, bl);
+    // bits 64..192; in characteristic 2 the subtractions are xors
+    let lr = clmul64(al ^ ar, bl ^ br) ^ ll ^ rr;
+
+    // sieve to upper 128..256 bits and lower 128 bits
+    let (lr_hi, lr_lo) = (lr >> 64, lr << 64);
+    (ll ^ lr_hi, rr ^ lr_lo)
+}
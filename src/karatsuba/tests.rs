@@ -0,0 +1,77 @@
+use ghash::{
+    universal_hash::{KeyInit, UniversalHash},
+    GHash,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+use crate::{
+    backend::Backend,
+    gfmul,
+    tests::{H, X_1, X_2},
+};
+
+#[test]
+fn test_clmul64() {
+    assert_eq!(clmul64(0, u64::MAX), 0);
+    assert_eq!(clmul64(1, 1), 1);
+    assert_eq!(clmul64(3, 3), 5);
+    assert_eq!(clmul64(3, 7), 9);
+    assert_eq!(clmul64(1 << 63, 1 << 63), 1 << 126);
+    // (1 + x + ... + x^63)^2 = 1 + x^2 + ... + x^126
+    assert_eq!(clmul64(u64::MAX, u64::MAX), (0..64).fold(0, |acc, i| acc | 1u128 << (2 * i)));
+    // integer multiplication carries; carryless multiplication does not
+    assert_ne!(clmul64(u64::MAX, u64::MAX), u64::MAX as u128 * u64::MAX as u128);
+}
+
+#[test]
+fn test_clmul128() {
+    assert_eq!(clmul128(1, 1), (0, 1));
+    assert_eq!(clmul128(1 << 127, 2), (1, 0));
+    assert_eq!(clmul128(1 << 127, 1 << 127), (1 << 126, 0));
+    // (x^64 + 1)^2 = x^128 + 1
+    assert_eq!(clmul128((1 << 64) | 1, (1 << 64) | 1), (1, 1));
+
+    let (a, b) = (parse_array_as_uint(X_1), parse_array_as_uint(X_2));
+    let (upper, lower) = clmul128(a, b);
+    assert_eq!(parse_u128_as_array(lower ^ crate::galois_reduce(upper)), gfmul(X_1, X_2));
+}
+
+#[test]
+fn test_karatsuba_gfmul_basis() {
+    // every single-bit pair, covering every carry position sadmode got wrong
+    for i in 0..128 {
+        for j in (0..128).step_by(7) {
+            let a = parse_u128_as_array(1 << i);
+            let b = parse_u128_as_array(1 << j);
+            assert_eq!(karatsuba_gfmul(a, b), gfmul(a, b), "x^{i} * x^{j}");
+        }
+    }
+}
+
+#[test]
+fn test_karatsuba_gfmul_vectors() {
+    assert_eq!(karatsuba_gfmul(H, X_1), gfmul(H, X_1));
+    assert_eq!(karatsuba_gfmul(X_1, X_2), gfmul(X_1, X_2));
+    assert_eq!(karatsuba_gfmul([0xff; 16], [0xff; 16]), gfmul([0xff; 16], [0xff; 16]));
+}
+
+#[test]
+fn test_karatsuba_gfmul_random() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..1000 {
+        let (a, b): ([u8; 16], [u8; 16]) = (rng.gen(), rng.gen());
+        assert_eq!(karatsuba_gfmul(a, b), gfmul(a, b));
+    }
+}
+
+#[test]
+fn test_backends_agree_with_rustcrypto() {
+    let mut ghash_rc = GHash::new(&H.into());
+    ghash_rc.update(&[X_1.into(), X_2.into()]);
+    let expected = ghash_rc.finalize();
+
+    for backend in Backend::ALL {
+        assert_eq!(backend.ghash(H, &[X_1, X_2]).as_slice(), expected.as_slice(), "{backend:?}");
+    }
+}
@@ -0,0 +1,306 @@
+//! Property tests of the field laws, for every multiplication and GHASH implementation in the
+//! crate.
+//!
+//! A new implementation gets the full set of checks by adding itself to [`gfmul_registry`],
+//! [`keyed_registry`] or [`ghash_registry`].
+
+use ark_bn254::Fr;
+use hex_literal::hex;
+use proptest::{
+    prelude::*,
+    test_runner::{Config, TestCaseError, TestRunner},
+};
+
+use crate::{
+    bristol::{and_minimized_gfmul_circuit, block_wires, gfmul_circuit, wires_block, HashKey},
+    byte_reverse,
+    cost::{bits_block, block_bits, table_mul, CostStrategy},
+    gfmul_with_witness, ghash, ghash_key_to_polyval,
+    r1cs::{
+        gadgets::{self, alloc_block, block_value, GfmulStrategy},
+        ConstraintSystem,
+    },
+    spread_gfmul_field,
+    tests::LONE,
+    Backend, Gf128, Ghash, GhashKey, GhashPowers, MulMatrix, Polyval, TableSize,
+};
+
+type Block = [u8; 16];
+type Gfmul = Box<dyn Fn(Block, Block) -> Block>;
+type GhashFn = Box<dyn Fn(Block, &[Block]) -> Block>;
+/// Builds, for a key, the multiplication by that key.
+type Keyed = Box<dyn Fn(Block) -> Box<dyn Fn(Block) -> Block>>;
+/// (name, whether it holds for `a`, `b`, `c`)
+type Law<'a> = (&'static str, &'a dyn Fn(Block, Block, Block) -> bool);
+/// (name, whether it holds for `a`, `c`)
+type KeyedLaw<'a> = (&'static str, &'a dyn Fn(Block, Block) -> bool);
+
+const ONE: Block = LONE;
+const ZERO: Block = [0; 16];
+
+/// Every gfmul in the crate, by name, with the number of cases per law: fewer for those that are
+/// slow in debug builds.
+fn gfmul_registry() -> Vec<(String, u32, Gfmul)> {
+    let mut registry: Vec<(String, u32, Gfmul)> = Backend::ALL
+        .iter()
+        .map(|b| (format!("{b:?}"), 64, Box::new(move |x, y| b.gfmul(x, y)) as Gfmul))
+        .collect();
+
+    registry.push((
+        "Gf128".into(),
+        64,
+        Box::new(|a, b| (Gf128::from_ghash_bytes(a) * Gf128::from_ghash_bytes(b)).to_ghash_bytes()),
+    ));
+    registry.push((
+        "GhashKey".into(),
+        64,
+        Box::new(|a, b| GhashKey::new(b, TableSize::Four).mul(a)),
+    ));
+    registry.push(("MulMatrix".into(), 8, Box::new(|a, b| MulMatrix::new(b).mul(a))));
+    registry.push(("spread_gfmul_field::<Fr>".into(), 32, Box::new(spread_gfmul_field::<Fr>)));
+    registry.push(("gfmul_with_witness".into(), 32, Box::new(|a, b| gfmul_with_witness(a, b).0)));
+    for strategy in GfmulStrategy::ALL {
+        let mul = move |a, b| {
+            let mut cs = ConstraintSystem::<Fr>::new();
+            let (x, y) = (alloc_block(&mut cs, a), alloc_block(&mut cs, b));
+            let out = gadgets::gfmul(&mut cs, strategy, &x, &y);
+            assert!(cs.is_satisfied());
+            block_value(&cs, &out)
+        };
+        registry.push((format!("r1cs::gadgets::gfmul({strategy:?})"), 2, Box::new(mul)));
+    }
+
+    let circuit = gfmul_circuit(CostStrategy::Karatsuba, HashKey::Secret).unwrap();
+    registry.push((
+        "bristol::gfmul_circuit".into(),
+        8,
+        Box::new(move |a, b| {
+            wires_block(&circuit.evaluate(&[block_wires(b), block_wires(a)]).unwrap()[0])
+        }),
+    ));
+    let circuit = and_minimized_gfmul_circuit(8);
+    registry.push((
+        "bristol::and_minimized_gfmul_circuit".into(),
+        8,
+        Box::new(move |a, b| {
+            wires_block(&circuit.evaluate(&[block_wires(b), block_wires(a)]).unwrap()[0])
+        }),
+    ));
+    // the table strategies take a public H, so they are checked in the keyed registry
+    for strategy in CostStrategy::ALL.into_iter().filter(|s| !s.public_h()) {
+        let mul =
+            move |a, b| bits_block(&strategy.mul(&block_bits(a, |x| x), b, &block_bits(b, |x| x)));
+        registry.push((format!("cost::{strategy:?}"), 32, Box::new(mul)));
+    }
+    registry
+}
+
+/// Every multiplication by a key fixed ahead of time, by name, with the number of cases per law.
+/// Each is built once for each of [`keys`], so a slow setup such as the Paar search is paid a
+/// handful of times rather than once per case.
+fn keyed_registry() -> Vec<(String, u32, Keyed)> {
+    let mut registry: Vec<(String, u32, Keyed)> = vec![
+        (
+            "MulMatrix".into(),
+            64,
+            Box::new(|h| {
+                let matrix = MulMatrix::new(h);
+                Box::new(move |a| matrix.mul(a))
+            }),
+        ),
+        (
+            "XorNetwork".into(),
+            64,
+            Box::new(|h| {
+                let network = MulMatrix::new(h).paar();
+                Box::new(move |a| network.mul(a))
+            }),
+        ),
+    ];
+    // a table lookup is a mux tree over every entry, so the 8-bit tables are slow in debug builds
+    for (strategy, size, cases) in
+        [(CostStrategy::Table4, TableSize::Four, 32), (CostStrategy::Table8, TableSize::Eight, 8)]
+    {
+        let mul: Keyed = Box::new(move |h| {
+            let key = GhashKey::new(h, size);
+            Box::new(move |a| bits_block(&table_mul(&block_bits(a, |x| x), &key)))
+        });
+        registry.push((format!("cost::{strategy:?}"), cases, mul));
+    }
+    registry
+}
+
+/// Every GHASH in the crate, by name.
+fn ghash_registry() -> Vec<(String, GhashFn)> {
+    let mut registry: Vec<(String, GhashFn)> = Backend::ALL
+        .iter()
+        .map(|b| (format!("{b:?}"), Box::new(move |h, x: &[Block]| b.ghash(h, x)) as GhashFn))
+        .collect();
+
+    registry.push(("ghash".into(), Box::new(ghash)));
+    registry.push((
+        "GhashKey".into(),
+        Box::new(|h, x: &[Block]| GhashKey::new(h, TableSize::Eight).ghash(x)),
+    ));
+    registry
+        .push(("GhashPowers".into(), Box::new(|h, x: &[Block]| GhashPowers::new(h, 4).ghash(x))));
+    registry.push((
+        "Ghash".into(),
+        Box::new(|h, x: &[Block]| {
+            let mut hasher = Ghash::new(h);
+            x.iter().for_each(|block| hasher.update(block));
+            hasher.finalize()
+        }),
+    ));
+    // GHASH(H, X) = ByteReverse(POLYVAL(mulX_POLYVAL(ByteReverse(H)), ByteReverse(X))), RFC 8452
+    registry.push((
+        "Polyval".into(),
+        Box::new(|h, x: &[Block]| {
+            let mut hasher = Polyval::new(ghash_key_to_polyval(h));
+            x.iter().for_each(|block| hasher.update(&byte_reverse(*block)));
+            byte_reverse(hasher.finalize())
+        }),
+    ));
+    registry
+}
+
+/// The keys of [`keyed_registry`]: one, a key and its inverse, and an unrelated key.
+fn keys() -> [Block; 4] {
+    let h = hex!("66e94bd4ef8a2c3b884cfa59ca342b2e");
+    [ONE, h, invert(h), hex!("b83b533708bf535d0aa6e52980d53b78")]
+}
+
+fn xor(a: Block, b: Block) -> Block { core::array::from_fn(|i| a[i] ^ b[i]) }
+
+/// a^-1 = a^(2^128 - 2), computed with [`Gf128`]; the law checks that `mul` agrees it is one.
+fn invert(a: Block) -> Block {
+    let a = Gf128::from_ghash_bytes(a);
+    // 2^128 - 2 is 127 ones followed by a zero
+    let acc = (1..127).fold(a, |acc, _| acc * acc * a);
+    (acc * acc).to_ghash_bytes()
+}
+
+fn runner(cases: u32) -> TestRunner {
+    TestRunner::new(Config { cases, failure_persistence: None, ..Config::default() })
+}
+
+/// Check every field law for `mul` on random inputs; the error names the law and the minimal
+/// failing input.
+fn check_field_laws(mul: &Gfmul, cases: u32) -> Result<(), String> {
+    let laws: [Law; 5] = [
+        ("commutativity", &|a, b, _| mul(a, b) == mul(b, a)),
+        ("associativity", &|a, b, c| mul(mul(a, b), c) == mul(a, mul(b, c))),
+        ("distributivity", &|a, b, c| mul(a, xor(b, c)) == xor(mul(a, b), mul(a, c))),
+        ("identity and zero", &|a, _, _| {
+            mul(a, ONE) == a && mul(ONE, a) == a && mul(a, ZERO) == ZERO
+        }),
+        ("inverses", &|a, _, _| a == ZERO || mul(a, invert(a)) == ONE),
+    ];
+
+    for (law, holds) in laws {
+        runner(cases)
+            .run(&any::<(Block, Block, Block)>(), |(a, b, c)| match holds(a, b, c) {
+                true => Ok(()),
+                false => Err(TestCaseError::fail(law)),
+            })
+            .map_err(|e| format!("{law}: {e}"))?;
+    }
+    Ok(())
+}
+
+/// Check the field laws that a multiplication by fixed keys can show, over the [`keys`], on
+/// random inputs; the error names the law and the minimal failing input.
+fn check_keyed_laws(keyed: &Keyed, cases: u32) -> Result<(), String> {
+    let keys = keys();
+    let muls: Vec<_> = keys.iter().map(|&h| keyed(h)).collect();
+    let [one, h, h_inv, k] = [&muls[0], &muls[1], &muls[2], &muls[3]];
+    let laws: [KeyedLaw; 5] = [
+        ("linearity", &|a, c| {
+            muls.iter().all(|m| m(xor(a, c)) == xor(m(a), m(c)) && m(ZERO) == ZERO)
+        }),
+        ("commutativity", &|a, _| h(k(a)) == k(h(a))),
+        ("identity", &|a, _| one(a) == a),
+        ("inverses", &|a, _| h(h_inv(a)) == a && h_inv(h(a)) == a),
+        // the laws above hold for any commuting linear maps, so tie each key to its product
+        ("agreement with gfmul", &|a, _| {
+            keys.iter().zip(&muls).all(|(&key, m)| m(a) == crate::gfmul(a, key))
+        }),
+    ];
+
+    for (law, holds) in laws {
+        runner(cases)
+            .run(&any::<(Block, Block)>(), |(a, c)| match holds(a, c) {
+                true => Ok(()),
+                false => Err(TestCaseError::fail(law)),
+            })
+            .map_err(|e| format!("{law}: {e}"))?;
+    }
+    Ok(())
+}
+
+/// Check `ghash` against the polynomial X_1·H^n + X_2·H^(n-1) + ... + X_n·H, evaluated with
+/// the reference gfmul.
+fn check_horner(ghash: &GhashFn) -> Result<(), String> {
+    let blocks = prop::collection::vec(any::<Block>(), 0..6);
+    runner(32)
+        .run(&(any::<Block>(), blocks), |(h, blocks)| {
+            let n = blocks.len();
+            let power = |k: usize| (0..k).fold(ONE, |acc, _| crate::gfmul(acc, h));
+            let expected = blocks
+                .iter()
+                .enumerate()
+                .fold(ZERO, |acc, (i, x)| xor(acc, crate::gfmul(*x, power(n - i))));
+            prop_assert_eq!(ghash(h, &blocks), expected);
+            Ok(())
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[test]
+fn test_field_laws() {
+    for (name, cases, mul) in gfmul_registry() {
+        check_field_laws(&mul, cases).unwrap_or_else(|e| panic!("{name}: {e}"));
+    }
+}
+
+#[test]
+fn test_keyed_laws() {
+    for (name, cases, keyed) in keyed_registry() {
+        check_keyed_laws(&keyed, cases).unwrap_or_else(|e| panic!("{name}: {e}"));
+    }
+}
+
+#[test]
+fn test_ghash_horner() {
+    for (name, ghash) in ghash_registry() {
+        check_horner(&ghash).unwrap_or_else(|e| panic!("{name}: {e}"));
+    }
+}
+
+#[test]
+fn test_laws_catch_broken_implementations() {
+    // an extra term that depends on the order of the operands
+    let broken: Gfmul = Box::new(|a, b| {
+        let mut out = crate::gfmul(a, b);
+        out[0] ^= a[0] & b[1];
+        out
+    });
+    assert!(check_field_laws(&broken, 64).unwrap_err().starts_with("commutativity"));
+
+    // a constant term, so the map is affine rather than linear
+    let affine: Keyed = Box::new(|h| {
+        Box::new(move |a| {
+            let mut out = crate::gfmul(a, h);
+            out[1] ^= h[1];
+            out
+        })
+    });
+    assert!(check_keyed_laws(&affine, 64).unwrap_err().starts_with("linearity"));
+    // ignoring the key satisfies every law but the last
+    let ignores_key: Keyed = Box::new(|_| Box::new(|a| a));
+    assert!(check_keyed_laws(&ignores_key, 64).unwrap_err().starts_with("agreement"));
+
+    let skips_last_block: GhashFn =
+        Box::new(|h, x: &[Block]| ghash(h, &x[..x.len().saturating_sub(1)]));
+    assert!(check_horner(&skips_last_block).is_err());
+}
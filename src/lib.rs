@@ -6,7 +6,55 @@
 #![allow(clippy::clone_on_copy)]
 #![allow(unused_mut)]
 
+mod aes;
+mod aggregate;
+mod backend;
+mod basis;
+pub mod bristol;
+pub mod cavp;
+mod circom;
+pub mod cost;
+mod ct;
+#[cfg(any(test, feature = "differential"))]
+pub mod differential;
+mod field;
+mod gcm;
+mod hasher;
+mod hint;
+mod karatsuba;
+#[cfg(test)] mod laws;
+mod matrix;
+pub mod pclmul;
+mod polyval;
+pub mod r1cs;
+mod spread;
+mod table;
 #[cfg(test)] mod tests;
+mod witness;
+pub mod wycheproof;
+
+pub use aes::Aes;
+pub use aggregate::GhashPowers;
+pub use backend::Backend;
+pub use basis::{basis, basis_mismatches, check_basis_equivalence, BasisMismatch};
+pub use circom::{gfmul_input_json, ghash_input_json, witness_json, BitOrder};
+pub use ct::ct_gfmul;
+pub use field::Gf128;
+pub use gcm::{inc32, AesGcm};
+pub use hasher::{ghash_gcm, Ghash};
+pub use hint::{reduction_hint, verify_reduction_hint, ReductionHint};
+pub use karatsuba::{clmul128, clmul64, karatsuba_gfmul};
+pub use matrix::{MulMatrix, XorNetwork};
+pub use pclmul::pclmul_gfmul;
+pub use polyval::{
+    byte_reverse, ghash_key_to_polyval, mul_x_ghash, mul_x_polyval, polyval, polyval_key_to_ghash,
+    Polyval,
+};
+pub use spread::{
+    clmul_spread, limb_slots, min_slot_bits, spread, spread_gfmul, spread_gfmul_field, SLOT_BITS,
+};
+pub use table::{GhashKey, TableSize};
+pub use witness::{gfmul_with_witness, ghash_with_witness, Witness, WitnessValue};
 
 pub fn ghash(hashkey: [u8; 16], blocks: &[[u8; 16]]) -> [u8; 16] {
     let mut x = [0u8; 16];
@@ -21,16 +69,17 @@ pub fn ghash(hashkey: [u8; 16], blocks: &[[u8; 16]]) -> [u8; 16] {
     x
 }
 
+/// Multiplication over the finite field $\text{GF}(2^{128})$, in the GHASH bit convention.
+///
+/// Computes the 256-bit carryless product of `a` and `b` as the xor of shifted partial products,
+/// then folds the upper 128 bits back into the field with `galois_reduce`.
 pub fn gfmul(a: [u8; 16], b: [u8; 16]) -> [u8; 16] {
     let a_uint = parse_array_as_uint(a);
     let b_arr = parse_array_as_bits(b);
 
-    // obtain a 128 bit array of each element in b_arr times a_uint
-    //
-    let products: Vec<u128> =
-        b_arr.iter().enumerate().map(|(i, bit)| if *bit { a_uint << i } else { 0 }).collect();
+    let products = partial_products(a_uint, b_arr);
 
-    // accumulate the xor of each 128-bit element into the pieces of upper_128 and lower_128
+    // accumulate the xor of each 256-bit element into the pieces of upper_128 and lower_128
     // a * b_arr[0]   => bits 0..128
     // a * b_arr[1]   => bits 1..129
     // ...
@@ -40,12 +89,28 @@ pub fn gfmul(a: [u8; 16], b: [u8; 16]) -> [u8; 16] {
     // lower128 contains bits 0..128
     // upper128 contains bits 128..256
     let (mut upper128, mut lower128) = (0, 0);
-    for product in products {
-        lower128 ^= product & ((1 << 128) - 1);
-        upper128 ^= product >> 128;
+    for (upper, lower) in products {
+        lower128 ^= lower;
+        upper128 ^= upper;
     }
 
-    todo!()
+    // upper128 encodes x^128 * upper128; reduce it back into the field
+    parse_u128_as_array(lower128 ^ galois_reduce(upper128))
+}
+
+/// obtain a 256 bit (upper, lower) pair for each element in b_arr times a_uint
+///
+/// the bits of a_uint shifted past position 127 land in the upper half
+fn partial_products(a_uint: u128, b_arr: [bool; 128]) -> Vec<(u128, u128)> {
+    b_arr
+        .iter()
+        .enumerate()
+        .map(|(i, bit)| match (*bit, i) {
+            (false, _) => (0, 0),
+            (true, 0) => (0, a_uint),
+            (true, i) => (a_uint >> (128 - i), a_uint << i),
+        })
+        .collect()
 }
 
 /// Multiplication over the finite field $\text{GF}(2^{128})$. Elements in this field are 128-bit
@@ -53,7 +118,9 @@ pub fn gfmul(a: [u8; 16], b: [u8; 16]) -> [u8; 16] {
 /// $x^{128} + x^7 + x^2 + x + 1$.
 ///
 /// sadmode_gfmul sadly makes incorrect assumptions about the feasibility of performing galois field
-/// arithmetic within the integers, and is irretrievably incorrect.
+/// arithmetic within the integers, and is irretrievably incorrect. See [`karatsuba_gfmul`] for the
+/// same split done with a carryless multiply, and [`spread_gfmul`] for integer multiplication made
+/// sound by spreading the bits apart.
 pub fn _sadmode_gfmul(a: [u8; 16], b: [u8; 16]) -> [u8; 16] {
     let (al, ar) = parse_array_as_pair(a);
     let (bl, br) = parse_array_as_pair(b);
@@ -116,6 +183,19 @@ fn galois_reduce(n: u128) -> u128 {
     m
 }
 
+/// Reduce the 256-bit product `upper128 * x^128 + lower128` modulo x^{128} + x^7 + x^2 + x + 1.
+///
+/// Equivalent to `lower128 ^ galois_reduce(upper128)`, but with shifts instead of a loop over 128
+/// bits: x^128 * upper = upper * (x^7 + x^2 + x + 1), and the at most 7 bits that spill past x^127
+/// are folded back in once more.
+fn reduce_product(upper128: u128, lower128: u128) -> u128 {
+    // bits of upper * (x^7 + x^2 + x + 1) at positions 128..135
+    let spill = (upper128 >> 127) ^ (upper128 >> 126) ^ (upper128 >> 121);
+    let upper128 = upper128 ^ spill;
+
+    lower128 ^ upper128 ^ (upper128 << 1) ^ (upper128 << 2) ^ (upper128 << 7)
+}
+
 /// Computes galois polynomial product (x^n)(x^7 + x^2 + x + 1) encoded as u128
 ///
 /// n=0  : [1, 1, 1, 0, 0, 0, 0, 1, 0...] => 135
@@ -194,10 +274,5 @@ fn reverse_byte(b: u8) -> u8 { (0..8).fold(0, |acc, i| acc | ((b >> (7 - i)) & 1
 /// 1 << 127 -> [ 0x80 0x00...]
 /// 1        -> [ 0x00... 0x01]
 fn parse_u128_as_array(n: u128) -> [u8; 16] {
-    let mut arr = [0; 16];
-    for i in 0..16 {
-        arr[i] = reverse_byte((n >> (i * 8)) as u8);
-        // println!("{i}th byte of {n}: {}", arr[i]);
-    }
-    arr
+    core::array::from_fn(|i| reverse_byte((n >> (i * 8)) as u8))
 }
@@ -0,0 +1,150 @@
+//! Multiplication by a public H as a linear map over GF(2).
+//!
+//! x ↦ x·H is linear in the bits of x, so it is a 128×128 bit matrix, and a circuit for it needs
+//! only XORs. Written out row by row it takes one XOR per set bit, about 8000 for a random H;
+//! [`MulMatrix::paar`] shares common pairs between rows to need fewer.
+
+use std::collections::HashMap;
+
+use crate::{
+    bristol::Circuit,
+    cost::{bits_block, Bit},
+    gfmul, parse_array_as_bits, parse_array_as_uint, parse_u128_as_array,
+};
+
+#[cfg(test)] mod tests;
+
+/// The matrix of x ↦ x·H. Bit `j` of `rows[i]` is set when input bit `j` contributes to output
+/// bit `i`, both indexed as by `parse_array_as_bits`: bit `k` is the coefficient of $x^k$.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MulMatrix {
+    rows: [u128; 128],
+}
+
+impl MulMatrix {
+    /// Column `j` is $x^j$·H.
+    pub fn new(h: [u8; 16]) -> Self {
+        let mut rows = [0u128; 128];
+        for j in 0..128 {
+            let column = parse_array_as_bits(gfmul(parse_u128_as_array(1 << j), h));
+            for (i, bit) in column.iter().enumerate() {
+                rows[i] |= (*bit as u128) << j;
+            }
+        }
+        Self { rows }
+    }
+
+    /// The input bits that output bit `i` depends on.
+    pub fn row(&self, i: usize) -> u128 { self.rows[i] }
+
+    /// Whether input bit `j` contributes to output bit `i`.
+    pub fn get(&self, i: usize, j: usize) -> bool { (self.rows[i] >> j) & 1 == 1 }
+
+    /// x·H, as a matrix-vector product.
+    pub fn mul(&self, x: [u8; 16]) -> [u8; 16] {
+        let x = parse_array_as_uint(x);
+        let out = self
+            .rows
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, row)| acc | (((row & x).count_ones() as u128) & 1) << i);
+        parse_u128_as_array(out)
+    }
+
+    /// XORs needed to compute each row on its own.
+    pub fn naive_xor_count(&self) -> usize {
+        self.rows.iter().map(|r| (r.count_ones() as usize).saturating_sub(1)).sum()
+    }
+
+    /// An XOR network for the matrix by Paar's greedy common-subexpression elimination: while
+    /// some pair of signals appears together in two or more rows, compute their XOR once and
+    /// substitute it into every such row. Ties go to the lowest pair, so the result is
+    /// deterministic.
+    pub fn paar(&self) -> XorNetwork {
+        let mut rows: Vec<Vec<usize>> =
+            self.rows.iter().map(|r| (0..128).filter(|j| (r >> j) & 1 == 1).collect()).collect();
+        let mut counts: HashMap<(usize, usize), usize> = HashMap::new();
+        for row in &rows {
+            for (k, a) in row.iter().enumerate() {
+                for b in &row[k + 1..] {
+                    *counts.entry((*a, *b)).or_default() += 1;
+                }
+            }
+        }
+
+        let mut gates = vec![];
+        loop {
+            let best = counts
+                .iter()
+                .filter(|(_, n)| **n >= 2)
+                .max_by_key(|((a, b), n)| (**n, usize::MAX - a, usize::MAX - b));
+            let Some((&(a, b), _)) = best else { break };
+            let c = 128 + gates.len();
+            gates.push((a, b));
+
+            for row in rows
+                .iter_mut()
+                .filter(|r| r.binary_search(&a).is_ok() && r.binary_search(&b).is_ok())
+            {
+                row.retain(|v| *v != a && *v != b);
+                for v in row.iter() {
+                    for key in [(a.min(*v), a.max(*v)), (b.min(*v), b.max(*v))] {
+                        let n = counts.get_mut(&key).unwrap();
+                        *n -= 1;
+                        if *n == 0 {
+                            counts.remove(&key);
+                        }
+                    }
+                    *counts.entry((*v, c)).or_default() += 1;
+                }
+                // c is the newest signal, so the row stays sorted
+                row.push(c);
+            }
+            counts.remove(&(a, b));
+        }
+
+        // what is left in each row is shared with no other row
+        let outputs = rows
+            .into_iter()
+            .map(|row| {
+                row.into_iter().reduce(|acc, v| {
+                    gates.push((acc, v));
+                    127 + gates.len()
+                })
+            })
+            .collect();
+        XorNetwork { gates, outputs }
+    }
+}
+
+/// A circuit of XOR gates over 128 input bits. Signals `0..128` are the inputs and signal
+/// `128 + g` is the output of gate `g`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XorNetwork {
+    /// the two signals each gate XORs
+    pub gates:   Vec<(usize, usize)>,
+    /// the signal of each output bit, `None` for a constant zero
+    pub outputs: Vec<Option<usize>>,
+}
+
+impl XorNetwork {
+    /// The number of XOR gates.
+    pub fn xor_count(&self) -> usize { self.gates.len() }
+
+    /// Run the network over 128 input bits.
+    pub fn apply<B: Bit>(&self, x: &[B]) -> Vec<B> {
+        let mut signals = x.to_vec();
+        for (a, b) in &self.gates {
+            signals.push(signals[*a] ^ signals[*b]);
+        }
+        self.outputs.iter().map(|o| o.map_or(B::constant(false), |s| signals[s])).collect()
+    }
+
+    /// x·H through the network.
+    pub fn mul(&self, x: [u8; 16]) -> [u8; 16] { bits_block(&self.apply(&parse_array_as_bits(x))) }
+
+    /// The network as a Bristol Fashion circuit with one 128-bit input and output.
+    pub fn to_circuit(&self) -> Circuit {
+        Circuit::build(&[128], |inputs| vec![self.apply(&inputs[0])])
+    }
+}
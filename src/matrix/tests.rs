@@ -0,0 +1,55 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+use crate::{bristol::block_wires, tests::*};
+
+#[test]
+fn test_matrix() {
+    // multiplying by 1 is the identity
+    let identity = MulMatrix::new(LONE);
+    assert!((0..128).all(|i| identity.row(i) == 1 << i));
+    assert_eq!(identity.naive_xor_count(), 0);
+
+    // multiplying by x shifts up one place, and x^127 wraps onto x^7 + x^2 + x + 1
+    let shift = MulMatrix::new(LTWO);
+    assert!(shift.get(1, 0) && !shift.get(0, 0));
+    assert!([0, 1, 2, 7].iter().all(|i| shift.get(*i, 127)));
+
+    let mut rng = StdRng::seed_from_u64(0);
+    for h in [H, RTHREE0, rng.gen()] {
+        let matrix = MulMatrix::new(h);
+        for x in [X_1, X_2, LONE, rng.gen()] {
+            assert_eq!(matrix.mul(x), gfmul(x, h));
+        }
+    }
+}
+
+#[test]
+fn test_paar() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for h in [H, rng.gen()] {
+        let matrix = MulMatrix::new(h);
+        let network = matrix.paar();
+        assert!(
+            network.xor_count() < matrix.naive_xor_count() / 2,
+            "naive {} XOR, paar {} XOR",
+            matrix.naive_xor_count(),
+            network.xor_count()
+        );
+        for x in [X_1, X_2, LONE, rng.gen(), rng.gen()] {
+            assert_eq!(network.mul(x), gfmul(x, h));
+        }
+
+        let circuit = network.to_circuit();
+        assert_eq!(circuit.and_count(), 0);
+        assert_eq!(circuit.xor_count(), network.xor_count());
+        let x: [u8; 16] = rng.gen();
+        assert_eq!(circuit.evaluate(&[block_wires(x)]).unwrap()[0], block_wires(gfmul(x, h)));
+    }
+
+    // small matrices come out exact
+    assert_eq!(MulMatrix::new(LONE).paar().xor_count(), 0);
+    let zero = MulMatrix::new([0; 16]).paar();
+    assert!(zero.outputs.iter().all(Option::is_none));
+    assert_eq!(zero.mul(X_1), [0; 16]);
+}
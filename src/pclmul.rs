@@ -0,0 +1,149 @@
+//! Hardware gfmul with the x86_64 `PCLMULQDQ` carryless-multiply instruction.
+//!
+//! The backend is picked at runtime with `is_x86_feature_detected!`; on other architectures, on
+//! CPUs without `pclmulqdq`, or when the `force_fallback` test hook is set, [`pclmul_gfmul`] falls
+//! back to a
+//! portable shift-and-xor. Like the instruction, the fallback masks instead of branching on the
+//! bits of its operands, so neither path's timing depends on the hash key.
+//!
+//! GHASH bytes are bit-reflected: `parse_array_as_uint` emulates this by reversing every
+//! byte and reading little-endian. The same integer is `u128::from_be_bytes(arr).reverse_bits()`,
+//! which lets the hardware path multiply plain polynomials with bit `i` as the coefficient of
+//! $x^i$.
+
+#[cfg(any(test, feature = "force-fallback"))]
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(test)]
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::reduce_product;
+
+#[cfg(test)] mod tests;
+
+/// Test hook: when set, [`pclmul_gfmul`] ignores the CPU features and takes the portable path.
+#[cfg(any(test, feature = "force-fallback"))]
+static FORCE_FALLBACK: AtomicBool = AtomicBool::new(false);
+
+/// Force (or stop forcing) the portable fallback in [`pclmul_gfmul`], e.g. to test it on a CPU
+/// that supports `pclmulqdq`. The flag is process-wide, so it is only built for tests and with the
+/// `force-fallback` feature.
+#[cfg(any(test, feature = "force-fallback"))]
+pub fn force_fallback(force: bool) { FORCE_FALLBACK.store(force, Ordering::SeqCst); }
+
+/// The fallback flag is global; tests that toggle it hold this lock.
+#[cfg(test)]
+static FALLBACK_LOCK: Mutex<()> = Mutex::new(());
+
+/// Holds [`FALLBACK_LOCK`] for a test, and stops forcing the fallback when dropped, also when the
+/// test panics.
+#[cfg(test)]
+pub(crate) struct FallbackGuard(MutexGuard<'static, ()>);
+
+#[cfg(test)]
+impl FallbackGuard {
+    /// Take the lock; a test that panicked while holding it does not fail the ones after it.
+    pub(crate) fn lock() -> Self {
+        Self(FALLBACK_LOCK.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+#[cfg(test)]
+impl Drop for FallbackGuard {
+    fn drop(&mut self) { force_fallback(false); }
+}
+
+/// Whether [`pclmul_gfmul`] will currently use the `PCLMULQDQ` instruction.
+pub fn uses_hardware() -> bool {
+    #[cfg(any(test, feature = "force-fallback"))]
+    if FORCE_FALLBACK.load(Ordering::SeqCst) {
+        return false;
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        std::is_x86_feature_detected!("pclmulqdq") && std::is_x86_feature_detected!("sse2")
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        false
+    }
+}
+
+/// Multiplication over the finite field $\text{GF}(2^{128})$ with `PCLMULQDQ` where available, and
+/// the portable shift-and-xor otherwise.
+pub fn pclmul_gfmul(a: [u8; 16], b: [u8; 16]) -> [u8; 16] {
+    #[cfg(target_arch = "x86_64")]
+    if uses_hardware() {
+        // SAFETY: the required target features were detected at runtime.
+        let product = unsafe { x86_64::clmul128(reflect(a), reflect(b)) };
+        return unreflect(reduce_product(product.0, product.1));
+    }
+
+    let (upper128, lower128) = soft_clmul128(reflect(a), reflect(b));
+    unreflect(reduce_product(upper128, lower128))
+}
+
+/// Carryless 128×128→256 multiplication without the instruction, returning (upper128, lower128):
+/// the shift-and-xor of [`crate::gfmul`] with a mask derived from each bit of `b` in place of the
+/// branch.
+pub(crate) fn soft_clmul128(a: u128, b: u128) -> (u128, u128) {
+    let (mut upper128, mut lower128) = (0u128, 0u128);
+    for i in 0..128 {
+        // all ones if bit i of b is set, else all zeros
+        let mask = 0u128.wrapping_sub((b >> i) & 1);
+        lower128 ^= (a << i) & mask;
+        // a >> (128 - i), without the out-of-range shift at i = 0
+        upper128 ^= ((a >> 1) >> (127 - i)) & mask;
+    }
+    (upper128, lower128)
+}
+
+/// Carryless 128×128→256 multiplication with `PCLMULQDQ` where available, and
+/// the portable shift-and-xor otherwise. Returns the unreduced product as (upper128, lower128).
+pub fn clmul128(a: u128, b: u128) -> (u128, u128) {
+    #[cfg(target_arch = "x86_64")]
+    if uses_hardware() {
+        // SAFETY: the required target features were detected at runtime.
+        return unsafe { x86_64::clmul128(a, b) };
+    }
+
+    soft_clmul128(a, b)
+}
+
+/// parse ghash-convention byte array to uint; agrees with [`crate::parse_array_as_uint`]
+pub(crate) fn reflect(arr: [u8; 16]) -> u128 { u128::from_be_bytes(arr).reverse_bits() }
+
+/// parse uint into ghash-convention byte array; agrees with [`crate::parse_u128_as_array`]
+pub(crate) fn unreflect(n: u128) -> [u8; 16] { n.reverse_bits().to_be_bytes() }
+
+#[cfg(target_arch = "x86_64")]
+mod x86_64 {
+    use core::arch::x86_64::{__m128i, _mm_clmulepi64_si128, _mm_set_epi64x};
+
+    /// Carryless 128×128→256 multiplication, returning (upper128, lower128).
+    ///
+    /// Schoolbook over 64-bit halves; `PCLMULQDQ` makes each of the four products one instruction.
+    ///
+    /// # Safety
+    /// The CPU must support `pclmulqdq` and `sse2`.
+    #[target_feature(enable = "pclmulqdq,sse2")]
+    pub(super) unsafe fn clmul128(a: u128, b: u128) -> (u128, u128) {
+        let a = _mm_set_epi64x((a >> 64) as i64, a as i64);
+        let b = _mm_set_epi64x((b >> 64) as i64, b as i64);
+
+        // imm8 selects the halves: bit 0 for a, bit 4 for b
+        let ll = to_u128(_mm_clmulepi64_si128(a, b, 0x00));
+        let lh = to_u128(_mm_clmulepi64_si128(a, b, 0x10));
+        let hl = to_u128(_mm_clmulepi64_si128(a, b, 0x01));
+        let hh = to_u128(_mm_clmulepi64_si128(a, b, 0x11));
+
+        let mid = lh ^ hl;
+        (hh ^ (mid >> 64), ll ^ (mid << 64))
+    }
+
+    fn to_u128(v: __m128i) -> u128 {
+        // SAFETY: __m128i and u128 are both 16 plain bytes; x86_64 is little-endian, so lane 0 is
+        // the low 64 bits.
+        unsafe { core::mem::transmute::<__m128i, u128>(v) }
+    }
+}
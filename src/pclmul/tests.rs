@@ -0,0 +1,73 @@
+use hex_literal::hex;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+use crate::{
+    gfmul, parse_array_as_uint, parse_u128_as_array,
+    tests::{H, X_1, X_2},
+};
+
+fn check_against_gfmul() {
+    assert_eq!(pclmul_gfmul(H, X_1), gfmul(H, X_1));
+    assert_eq!(pclmul_gfmul(X_1, X_2), gfmul(X_1, X_2));
+    assert_eq!(pclmul_gfmul([0xff; 16], [0xff; 16]), gfmul([0xff; 16], [0xff; 16]));
+
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..1000 {
+        let (a, b): ([u8; 16], [u8; 16]) = (rng.gen(), rng.gen());
+        assert_eq!(pclmul_gfmul(a, b), gfmul(a, b));
+    }
+}
+
+#[test]
+fn test_reflect() {
+    for arr in [H, X_1, X_2, hex!("80000000000000000000000000000000")] {
+        assert_eq!(reflect(arr), parse_array_as_uint(arr));
+        assert_eq!(unreflect(reflect(arr)), arr);
+    }
+    assert_eq!(unreflect(1 << 127), parse_u128_as_array(1 << 127));
+}
+
+#[test]
+fn test_clmul128() {
+    let _guard = FallbackGuard::lock();
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..1000 {
+        let (a, b): (u128, u128) = (rng.gen(), rng.gen());
+        force_fallback(false);
+        let hardware = clmul128(a, b);
+        force_fallback(true);
+        assert_eq!(hardware, clmul128(a, b));
+    }
+}
+
+#[test]
+fn test_pclmul_gfmul_detected() {
+    let _guard = FallbackGuard::lock();
+    force_fallback(false);
+    #[cfg(target_arch = "x86_64")]
+    assert_eq!(uses_hardware(), std::is_x86_feature_detected!("pclmulqdq"));
+    check_against_gfmul();
+}
+
+#[test]
+fn test_pclmul_gfmul_forced_fallback() {
+    let _guard = FallbackGuard::lock();
+    force_fallback(true);
+    assert!(!uses_hardware());
+    check_against_gfmul();
+}
+
+#[test]
+fn test_fallback_guard_survives_panics() {
+    let panicked = std::panic::catch_unwind(|| {
+        let _guard = FallbackGuard::lock();
+        force_fallback(true);
+        panic!("a failing test that forced the fallback");
+    });
+    assert!(panicked.is_err());
+
+    // the lock is not poisoned for later tests, and the flag was reset
+    let _guard = FallbackGuard::lock();
+    assert!(!FORCE_FALLBACK.load(Ordering::SeqCst));
+}
@@ -0,0 +1,69 @@
+//! POLYVAL (RFC 8452) on top of the crate's GHASH.
+//!
+//! POLYVAL works over the same field as GHASH, but with little-endian bytes, un-reflected bits,
+//! and the Montgomery-style product dot(a, b) = a·b·x^{-128}. RFC 8452 appendix A relates the two:
+//!
+//! POLYVAL(H, X_1, ..., X_n) =
+//!     ByteReverse(GHASH(mulX_GHASH(ByteReverse(H)), ByteReverse(X_1), ..., ByteReverse(X_n)))
+//!
+//! and conversely with mulX_POLYVAL, so a single [`crate::gfmul`] core serves both conventions.
+
+use crate::{ghash, hasher::Ghash, parse_array_as_uint, parse_u128_as_array};
+
+#[cfg(test)] mod tests;
+
+/// x^128 = x^127 + x^126 + x^121 + 1 in the POLYVAL convention, where bit `i` of the little-endian
+/// integer is the coefficient of $x^i$.
+const POLYVAL_REDUCTION: u128 = (1 << 127) | (1 << 126) | (1 << 121) | 1;
+
+/// Reverse the byte order of a block.
+pub fn byte_reverse(mut block: [u8; 16]) -> [u8; 16] {
+    block.reverse();
+    block
+}
+
+/// Multiply a GHASH-convention field element by x.
+pub fn mul_x_ghash(block: [u8; 16]) -> [u8; 16] {
+    let n = parse_array_as_uint(block);
+    // x^128 = x^7 + x^2 + x + 1
+    parse_u128_as_array((n << 1) ^ if n >> 127 == 1 { 135 } else { 0 })
+}
+
+/// Multiply a POLYVAL-convention field element by x.
+pub fn mul_x_polyval(block: [u8; 16]) -> [u8; 16] {
+    let n = u128::from_le_bytes(block);
+    ((n << 1) ^ if n >> 127 == 1 { POLYVAL_REDUCTION } else { 0 }).to_le_bytes()
+}
+
+/// The GHASH key that computes POLYVAL under the POLYVAL key `h`: mulX_GHASH(ByteReverse(H)).
+pub fn polyval_key_to_ghash(h: [u8; 16]) -> [u8; 16] { mul_x_ghash(byte_reverse(h)) }
+
+/// The POLYVAL key that computes GHASH under the GHASH key `h`: mulX_POLYVAL(ByteReverse(H)).
+pub fn ghash_key_to_polyval(h: [u8; 16]) -> [u8; 16] { mul_x_polyval(byte_reverse(h)) }
+
+/// POLYVAL of `blocks` under the POLYVAL-convention hash key `h`.
+pub fn polyval(h: [u8; 16], blocks: &[[u8; 16]]) -> [u8; 16] {
+    let blocks: Vec<[u8; 16]> = blocks.iter().copied().map(byte_reverse).collect();
+    byte_reverse(ghash(polyval_key_to_ghash(h), &blocks))
+}
+
+/// Incremental POLYVAL under a fixed hash key; the counterpart of [`Ghash`].
+#[derive(Clone, Debug)]
+pub struct Polyval(Ghash);
+
+impl Polyval {
+    /// A hasher under the POLYVAL-convention hash key `h`.
+    pub fn new(h: [u8; 16]) -> Self { Self(Ghash::reversed(polyval_key_to_ghash(h))) }
+
+    /// Absorb `data` as a continuation of the message; a trailing partial block stays buffered.
+    pub fn update(&mut self, data: &[u8]) { self.0.update(data) }
+
+    /// Absorb `data`, then zero-pad to the next block boundary.
+    pub fn update_padded(&mut self, data: &[u8]) { self.0.update_padded(data) }
+
+    /// The hash of everything absorbed so far, zero-padding a buffered partial block.
+    pub fn finalize(&self) -> [u8; 16] { byte_reverse(self.0.finalize()) }
+
+    /// Forget all input, keeping the hash key.
+    pub fn reset(&mut self) { self.0.reset() }
+}
@@ -0,0 +1,89 @@
+use hex_literal::hex;
+use polyval::{
+    universal_hash::{KeyInit, UniversalHash},
+    Polyval as RcPolyval,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+use crate::tests::{H, X_1, X_2};
+
+#[test]
+fn test_rfc8452_appendix_a() {
+    // the crate's GHASH vectors are the ones RFC 8452 uses for POLYVAL
+    assert_eq!(polyval(H, &[X_1, X_2]), hex!("f7a3b47b846119fae5b7866cf5e5b77e"));
+
+    assert_eq!(byte_reverse(H), hex!("7b754bba26f8311d7642925847936225"));
+    assert_eq!(polyval_key_to_ghash(H), hex!("dcbaa5dd137c188ebb21492c23c9b112"));
+    assert_eq!(
+        ghash(polyval_key_to_ghash(H), &[byte_reverse(X_1), byte_reverse(X_2)]),
+        hex!("7eb7e5f56c86b7e5fa1961847bb4a3f7")
+    );
+}
+
+#[test]
+fn test_rfc8452_appendix_c() {
+    // AEAD_AES_128_GCM_SIV, 8-byte plaintext: the POLYVAL input is the padded plaintext followed
+    // by the length block
+    let h = hex!("d9b360279694941ac5dbc6987ada7377");
+    let mut hasher = Polyval::new(h);
+    hasher.update_padded(&hex!("0100000000000000"));
+    hasher.update(&hex!("00000000000000004000000000000000"));
+    assert_eq!(hasher.finalize(), hex!("eb93b7740962c5e49d2a90a7dc5cec74"));
+}
+
+#[test]
+fn test_mul_x() {
+    assert_eq!(
+        mul_x_ghash(hex!("80000000000000000000000000000000")),
+        hex!("40000000000000000000000000000000")
+    );
+    assert_eq!(
+        mul_x_ghash(hex!("00000000000000000000000000000001")),
+        hex!("e1000000000000000000000000000000")
+    );
+    assert_eq!(
+        mul_x_polyval(hex!("01000000000000000000000000000000")),
+        hex!("02000000000000000000000000000000")
+    );
+    assert_eq!(
+        mul_x_polyval(hex!("00000000000000000000000000000080")),
+        hex!("010000000000000000000000000000c2")
+    );
+}
+
+#[test]
+fn test_ghash_identity() {
+    // GHASH(H, X_1, ..., X_n) = ByteReverse(POLYVAL(mulX_POLYVAL(ByteReverse(H)),
+    // ByteReverse(X_i)))
+    let mut rng = StdRng::seed_from_u64(0);
+    for len in 0..8 {
+        let h: [u8; 16] = rng.gen();
+        let blocks: Vec<[u8; 16]> = (0..len).map(|_| rng.gen()).collect();
+        let reversed: Vec<[u8; 16]> = blocks.iter().copied().map(byte_reverse).collect();
+        assert_eq!(ghash(h, &blocks), byte_reverse(polyval(ghash_key_to_polyval(h), &reversed)));
+    }
+}
+
+#[test]
+fn test_matches_rustcrypto() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for len in [0, 1, 15, 16, 17, 48, 100] {
+        let h: [u8; 16] = rng.gen();
+        let data: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+
+        let mut polyval_rc = RcPolyval::new(&h.into());
+        polyval_rc.update_padded(&data);
+        let expected = polyval_rc.finalize();
+
+        let mut hasher = Polyval::new(h);
+        let (head, tail) = data.split_at(len / 3);
+        hasher.update(head);
+        hasher.update(tail);
+        assert_eq!(expected.as_slice(), hasher.finalize());
+
+        hasher.reset();
+        hasher.update_padded(&data);
+        assert_eq!(expected.as_slice(), hasher.finalize());
+    }
+}
@@ -0,0 +1,152 @@
+//! A small rank-1 constraint system over a prime field, for measuring circuit strategies for
+//! gfmul in Rust before writing circom.
+//!
+//! A constraint is `<A, w> * <B, w> = <C, w>` for linear combinations A, B, C over the witness w,
+//! where `w[0]` is the constant one. The builder computes witness values as wires are allocated,
+//! so every circuit can be checked for satisfiability immediately; see [`gadgets`] for gfmul.
+
+use core::ops::{Add, Mul, Neg, Sub};
+
+use ark_ff::PrimeField;
+
+pub mod gadgets;
+#[cfg(test)] mod tests;
+
+/// A wire in a [`ConstraintSystem`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Variable(usize);
+
+impl Variable {
+    /// The wire fixed to the constant one.
+    pub const ONE: Variable = Variable(0);
+
+    /// The position of this wire in the witness.
+    pub fn index(self) -> usize { self.0 }
+}
+
+/// A sum of field multiples of wires.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinearCombination<F: PrimeField>(Vec<(Variable, F)>);
+
+impl<F: PrimeField> LinearCombination<F> {
+    /// The empty sum.
+    pub fn zero() -> Self { Self(Vec::new()) }
+
+    /// The constant `c`.
+    pub fn constant(c: F) -> Self { Self(vec![(Variable::ONE, c)]) }
+
+    /// The terms of this combination, possibly with repeated wires.
+    pub fn terms(&self) -> &[(Variable, F)] { &self.0 }
+}
+
+impl<F: PrimeField> From<Variable> for LinearCombination<F> {
+    fn from(v: Variable) -> Self { Self(vec![(v, F::one())]) }
+}
+
+impl<F: PrimeField> Add for LinearCombination<F> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        self.0.extend(rhs.0);
+        self
+    }
+}
+
+impl<F: PrimeField> Neg for LinearCombination<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self { self * -F::one() }
+}
+
+impl<F: PrimeField> Sub for LinearCombination<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self { self + -rhs }
+}
+
+impl<F: PrimeField> Mul<F> for LinearCombination<F> {
+    type Output = Self;
+
+    fn mul(self, c: F) -> Self { Self(self.0.into_iter().map(|(v, k)| (v, k * c)).collect()) }
+}
+
+/// A single rank-1 constraint `a * b = c`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Constraint<F: PrimeField> {
+    pub a: LinearCombination<F>,
+    pub b: LinearCombination<F>,
+    pub c: LinearCombination<F>,
+}
+
+/// Constraints together with the witness that should satisfy them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConstraintSystem<F: PrimeField> {
+    witness:     Vec<F>,
+    constraints: Vec<Constraint<F>>,
+}
+
+impl<F: PrimeField> Default for ConstraintSystem<F> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<F: PrimeField> ConstraintSystem<F> {
+    /// An empty system with only the constant-one wire.
+    pub fn new() -> Self { Self { witness: vec![F::one()], constraints: Vec::new() } }
+
+    /// Allocate a wire holding `value`, with no constraints on it.
+    pub fn alloc(&mut self, value: F) -> Variable {
+        self.witness.push(value);
+        Variable(self.witness.len() - 1)
+    }
+
+    /// Allocate a boolean wire, enforcing b * (1 - b) = 0.
+    pub fn alloc_bit(&mut self, value: bool) -> Variable {
+        let b = self.alloc(F::from(value));
+        self.enforce(
+            b.into(),
+            LinearCombination::constant(F::one()) - b.into(),
+            LinearCombination::zero(),
+        );
+        b
+    }
+
+    /// Add the constraint `a * b = c`.
+    pub fn enforce(
+        &mut self,
+        a: LinearCombination<F>,
+        b: LinearCombination<F>,
+        c: LinearCombination<F>,
+    ) {
+        self.constraints.push(Constraint { a, b, c });
+    }
+
+    /// The value of a wire.
+    pub fn value(&self, v: Variable) -> F { self.witness[v.0] }
+
+    /// Overwrite the value of a wire, e.g. to check that a tampered witness is rejected.
+    pub fn set_value(&mut self, v: Variable, value: F) { self.witness[v.0] = value; }
+
+    /// The value of a linear combination under the current witness.
+    pub fn eval(&self, lc: &LinearCombination<F>) -> F {
+        lc.0.iter().map(|(v, k)| self.witness[v.0] * k).sum()
+    }
+
+    /// The number of constraints.
+    pub fn num_constraints(&self) -> usize { self.constraints.len() }
+
+    /// The number of wires, including the constant one.
+    pub fn num_variables(&self) -> usize { self.witness.len() }
+
+    /// The constraints, in the order they were added.
+    pub fn constraints(&self) -> &[Constraint<F>] { &self.constraints }
+
+    /// The index of the first constraint the witness violates, if any.
+    pub fn which_is_unsatisfied(&self) -> Option<usize> {
+        self.constraints
+            .iter()
+            .position(|Constraint { a, b, c }| self.eval(a) * self.eval(b) != self.eval(c))
+    }
+
+    /// Whether the witness satisfies every constraint.
+    pub fn is_satisfied(&self) -> bool { self.which_is_unsatisfied().is_none() }
+}
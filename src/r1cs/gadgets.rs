@@ -0,0 +1,209 @@
+//! Gadgets that arithmetize gfmul over a prime field.
+//!
+//! A GF(2) value is carried as a [`ParitySum`]: a linear combination of boolean wires whose
+//! integer value has the bit as its parity. Xor is then integer addition and costs nothing, and a
+//! sum only needs to be decomposed into an actual bit, with [`parity`], before it is multiplied or
+//! output. Every product of two bits is one constraint, so the strategies differ in how many bit
+//! products they need:
+//!
+//! - [`clmul_bit_serial`]: every a_i·b_j, as in [`crate::gfmul`]; 128·128 products.
+//! - [`clmul_karatsuba`]: the 64-bit halves split of [`crate::karatsuba_gfmul`]; 3·64·64 products
+//!   plus 128 xors for the half sums.
+//!
+//! Reduction modulo x^128 + x^7 + x^2 + x + 1, as in `galois_reduce`, is linear and free.
+
+use ark_ff::PrimeField;
+
+use super::{ConstraintSystem, LinearCombination, Variable};
+use crate::parse_array_as_bits;
+
+/// A gfmul arithmetization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GfmulStrategy {
+    /// Every partial product a_i·b_j, see [`clmul_bit_serial`].
+    BitSerial,
+    /// One level of Karatsuba over 64-bit halves, see [`clmul_karatsuba`].
+    Karatsuba,
+}
+
+impl GfmulStrategy {
+    /// Every strategy, for comparison.
+    pub const ALL: [GfmulStrategy; 2] = [GfmulStrategy::BitSerial, GfmulStrategy::Karatsuba];
+}
+
+/// A GF(2) value held as a linear combination of boolean wires whose integer value is at most
+/// `max`; the bit is its parity.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParitySum<F: PrimeField> {
+    pub lc:  LinearCombination<F>,
+    pub max: usize,
+}
+
+impl<F: PrimeField> ParitySum<F> {
+    /// The empty sum, i.e. the bit 0.
+    pub fn zero() -> Self { Self { lc: LinearCombination::zero(), max: 0 } }
+
+    /// Xor, as integer addition.
+    pub fn add(&mut self, other: &ParitySum<F>) {
+        self.lc = self.lc.clone() + other.lc.clone();
+        self.max += other.max;
+    }
+}
+
+impl<F: PrimeField> From<Variable> for ParitySum<F> {
+    fn from(v: Variable) -> Self { Self { lc: v.into(), max: 1 } }
+}
+
+/// Allocate the 128 bits of a GHASH-convention block, index `i` the coefficient of $x^i$.
+pub fn alloc_block<F: PrimeField>(cs: &mut ConstraintSystem<F>, block: [u8; 16]) -> Vec<Variable> {
+    parse_array_as_bits(block).into_iter().map(|bit| cs.alloc_bit(bit)).collect()
+}
+
+/// Read a block back out of 128 bit wires.
+pub fn block_value<F: PrimeField>(cs: &ConstraintSystem<F>, bits: &[Variable]) -> [u8; 16] {
+    let n = bits
+        .iter()
+        .enumerate()
+        .fold(0u128, |acc, (i, b)| acc | ((cs.value(*b) == F::one()) as u128) << i);
+    crate::parse_u128_as_array(n)
+}
+
+/// a AND b, for boolean a and b: one constraint a * b = c.
+pub fn and<F: PrimeField>(cs: &mut ConstraintSystem<F>, a: Variable, b: Variable) -> Variable {
+    let c = cs.alloc(cs.value(a) * cs.value(b));
+    cs.enforce(a.into(), b.into(), c.into());
+    c
+}
+
+/// a XOR b, for boolean a and b: one constraint 2a * b = a + b - c.
+pub fn xor<F: PrimeField>(cs: &mut ConstraintSystem<F>, a: Variable, b: Variable) -> Variable {
+    let c = cs.alloc(F::from(cs.value(a) != cs.value(b)));
+    let two = F::from(2u8);
+    cs.enforce(
+        LinearCombination::from(a) * two,
+        b.into(),
+        LinearCombination::from(a) + b.into() - c.into(),
+    );
+    c
+}
+
+/// Decompose a [`ParitySum`] into its parity bit c, enforcing sum = c + 2·q for a boolean
+/// decomposition of q. Costs 2 + bits(max / 2) constraints; a lone bit wire is returned as is.
+pub fn parity<F: PrimeField>(cs: &mut ConstraintSystem<F>, sum: ParitySum<F>) -> Variable {
+    if let [(v, k)] = sum.lc.terms() {
+        if sum.max <= 1 && *k == F::one() {
+            return *v;
+        }
+    }
+
+    let value = cs.eval(&sum.lc).into_bigint().as_ref()[0] as usize;
+    let c = cs.alloc_bit(value & 1 == 1);
+    let q_bits = usize::BITS - (sum.max / 2).leading_zeros();
+    let mut rhs = LinearCombination::from(c);
+    for j in 0..q_bits {
+        let k = cs.alloc_bit((value >> (j + 1)) & 1 == 1);
+        rhs = rhs + LinearCombination::from(k) * F::from(1u64 << (j + 1));
+    }
+    cs.enforce(sum.lc, LinearCombination::constant(F::one()), rhs);
+    c
+}
+
+/// The carryless product of `a` and `b` as unreduced column sums, one per power of x: every
+/// a_i·b_j is an [`and`] added into column i + j.
+pub fn clmul_bit_serial<F: PrimeField>(
+    cs: &mut ConstraintSystem<F>,
+    a: &[Variable],
+    b: &[Variable],
+) -> Vec<ParitySum<F>> {
+    let mut columns = vec![ParitySum::zero(); a.len() + b.len()];
+    for (i, a_i) in a.iter().enumerate() {
+        for (j, b_j) in b.iter().enumerate() {
+            let product = and(cs, *a_i, *b_j);
+            columns[i + j].add(&product.into());
+        }
+    }
+    columns
+}
+
+/// The carryless product of two 128-bit `a` and `b` with a single level of Karatsuba:
+///
+/// rr = ar·br                   ; columns 0..128
+/// lr = (al+ar)(bl+br) - ll - rr ; columns 64..192
+/// ll = al·bl                   ; columns 128..256
+///
+/// The half sums al+ar and bl+br are multiplied, so they are [`xor`]ed into actual bits; the
+/// combination of the three products is free.
+pub fn clmul_karatsuba<F: PrimeField>(
+    cs: &mut ConstraintSystem<F>,
+    a: &[Variable],
+    b: &[Variable],
+) -> Vec<ParitySum<F>> {
+    assert_eq!((a.len(), b.len()), (128, 128));
+    let (ar, al) = a.split_at(64);
+    let (br, bl) = b.split_at(64);
+
+    let rr = clmul_bit_serial(cs, ar, br);
+    let ll = clmul_bit_serial(cs, al, bl);
+    let a_sum: Vec<Variable> = (0..64).map(|i| xor(cs, al[i], ar[i])).collect();
+    let b_sum: Vec<Variable> = (0..64).map(|i| xor(cs, bl[i], br[i])).collect();
+    let mm = clmul_bit_serial(cs, &a_sum, &b_sum);
+
+    let mut columns = vec![ParitySum::zero(); 256];
+    for k in 0..128 {
+        columns[k].add(&rr[k]);
+        columns[k + 128].add(&ll[k]);
+        // lr = mm + ll + rr
+        columns[k + 64].add(&mm[k]);
+        columns[k + 64].add(&ll[k]);
+        columns[k + 64].add(&rr[k]);
+    }
+    columns
+}
+
+/// Fold columns 128..256 of a product back into columns 0..128, with
+/// x^128 = x^7 + x^2 + x + 1. Linear, so it adds no constraints.
+pub fn reduce<F: PrimeField>(mut columns: Vec<ParitySum<F>>) -> Vec<ParitySum<F>> {
+    columns.resize(256, ParitySum::zero());
+    // descending, so columns that land at 128 or above again are folded once more
+    for k in (128..256).rev() {
+        let column = core::mem::replace(&mut columns[k], ParitySum::zero());
+        for j in [0, 1, 2, 7] {
+            columns[k - 128 + j].add(&column);
+        }
+    }
+    columns.truncate(128);
+    columns
+}
+
+/// `galois_reduce` over 128 bit wires: x^128 · upper, reduced into 128 bit wires.
+pub fn galois_reduce<F: PrimeField>(
+    cs: &mut ConstraintSystem<F>,
+    upper: &[Variable],
+) -> Vec<Variable> {
+    let mut columns = vec![ParitySum::zero(); 128];
+    columns.extend(upper.iter().map(|v| ParitySum::from(*v)));
+    reduce(columns).into_iter().map(|column| parity(cs, column)).collect()
+}
+
+/// gfmul over 128 bit wires each, returning the 128 product bits.
+pub fn gfmul<F: PrimeField>(
+    cs: &mut ConstraintSystem<F>,
+    strategy: GfmulStrategy,
+    a: &[Variable],
+    b: &[Variable],
+) -> Vec<Variable> {
+    let columns = match strategy {
+        GfmulStrategy::BitSerial => clmul_bit_serial(cs, a, b),
+        GfmulStrategy::Karatsuba => clmul_karatsuba(cs, a, b),
+    };
+    reduce(columns).into_iter().map(|column| parity(cs, column)).collect()
+}
+
+/// The number of constraints [`gfmul`] adds with `strategy`, excluding the input bits.
+pub fn gfmul_constraint_count<F: PrimeField>(strategy: GfmulStrategy) -> usize {
+    let mut cs = ConstraintSystem::<F>::new();
+    let (a, b) = (alloc_block(&mut cs, [0; 16]), alloc_block(&mut cs, [0; 16]));
+    let before = cs.num_constraints();
+    gfmul(&mut cs, strategy, &a, &b);
+    cs.num_constraints() - before
+}
@@ -0,0 +1,120 @@
+use ark_bn254::Fr;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{gadgets::*, *};
+use crate::{
+    galois_reduce as galois_reduce_uint, gfmul as gfmul_bytes, parse_array_as_uint,
+    parse_u128_as_array, tests::*,
+};
+
+#[test]
+fn test_linear_combination() {
+    let mut cs = ConstraintSystem::<Fr>::new();
+    let (x, y) = (cs.alloc(Fr::from(3u8)), cs.alloc(Fr::from(5u8)));
+    let lc = LinearCombination::from(x) * Fr::from(2u8) + y.into()
+        - LinearCombination::constant(Fr::from(1u8));
+    assert_eq!(cs.eval(&lc), Fr::from(10u8));
+    assert_eq!(cs.eval(&-lc), -Fr::from(10u8));
+    assert_eq!(cs.eval(&LinearCombination::zero()), Fr::from(0u8));
+    assert_eq!(cs.num_variables(), 3);
+    assert_eq!(Variable::ONE.index(), 0);
+}
+
+#[test]
+fn test_enforce_and_tamper() {
+    let mut cs = ConstraintSystem::<Fr>::new();
+    let (x, y) = (cs.alloc(Fr::from(3u8)), cs.alloc(Fr::from(5u8)));
+    let z = cs.alloc(Fr::from(15u8));
+    cs.enforce(x.into(), y.into(), z.into());
+    assert!(cs.is_satisfied());
+    cs.set_value(z, Fr::from(16u8));
+    assert_eq!(cs.which_is_unsatisfied(), Some(0));
+}
+
+#[test]
+fn test_alloc_bit() {
+    let mut cs = ConstraintSystem::<Fr>::new();
+    let b = cs.alloc_bit(true);
+    assert_eq!(cs.num_constraints(), 1);
+    assert!(cs.is_satisfied());
+    cs.set_value(b, Fr::from(2u8));
+    assert!(!cs.is_satisfied());
+}
+
+#[test]
+fn test_and_xor() {
+    for (x, y) in [(false, false), (false, true), (true, false), (true, true)] {
+        let mut cs = ConstraintSystem::<Fr>::new();
+        let (a, b) = (cs.alloc_bit(x), cs.alloc_bit(y));
+        let (c, d) = (and(&mut cs, a, b), xor(&mut cs, a, b));
+        assert_eq!(cs.value(c), Fr::from(x && y));
+        assert_eq!(cs.value(d), Fr::from(x != y));
+        assert_eq!(cs.num_constraints(), 4);
+        assert!(cs.is_satisfied());
+
+        cs.set_value(d, Fr::from(x == y));
+        assert!(!cs.is_satisfied());
+    }
+}
+
+#[test]
+fn test_parity() {
+    let mut cs = ConstraintSystem::<Fr>::new();
+    let bits: Vec<Variable> = [true, true, false, true, true].map(|b| cs.alloc_bit(b)).to_vec();
+    let mut sum = ParitySum::zero();
+    bits.iter().for_each(|b| sum.add(&(*b).into()));
+    let before = cs.num_constraints();
+    let p = parity(&mut cs, sum);
+    assert_eq!(cs.value(p), Fr::from(0u8));
+    // c, q in 0..=2 as two bits, and the decomposition
+    assert_eq!(cs.num_constraints() - before, 4);
+    assert!(cs.is_satisfied());
+    cs.set_value(p, Fr::from(1u8));
+    assert!(!cs.is_satisfied());
+
+    // a lone bit costs nothing
+    let before = cs.num_constraints();
+    assert_eq!(parity(&mut cs, bits[0].into()), bits[0]);
+    assert_eq!(cs.num_constraints(), before);
+}
+
+#[test]
+fn test_galois_reduce_gadget() {
+    for n in [0, 1, 2, 3, 5, 1u128 << 120, 1u128 << 121, (1u128 << 121) + (1u128 << 120), u128::MAX]
+    {
+        let mut cs = ConstraintSystem::<Fr>::new();
+        let upper = alloc_block(&mut cs, parse_u128_as_array(n));
+        let reduced = galois_reduce(&mut cs, &upper);
+        assert_eq!(parse_array_as_uint(block_value(&cs, &reduced)), galois_reduce_uint(n));
+        assert!(cs.is_satisfied());
+    }
+}
+
+#[test]
+fn test_gfmul_gadget() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let random: ([u8; 16], [u8; 16]) = (rng.gen(), rng.gen());
+    for strategy in GfmulStrategy::ALL {
+        for (x, y) in [(LONE, RONE), (LC, RTHREE), (RTHREE0, RTHREE0), (H, X_1), random] {
+            let mut cs = ConstraintSystem::<Fr>::new();
+            let (a, b) = (alloc_block(&mut cs, x), alloc_block(&mut cs, y));
+            let out = gfmul(&mut cs, strategy, &a, &b);
+            assert_eq!(block_value(&cs, &out), gfmul_bytes(x, y), "{strategy:?}");
+            assert!(cs.is_satisfied(), "{strategy:?}");
+
+            // flipping an output bit breaks satisfiability
+            let flipped = Fr::from(1u8) - cs.value(out[5]);
+            cs.set_value(out[5], flipped);
+            assert!(!cs.is_satisfied(), "{strategy:?}");
+        }
+    }
+}
+
+#[test]
+fn test_constraint_counts() {
+    let bit_serial = gfmul_constraint_count::<Fr>(GfmulStrategy::BitSerial);
+    let karatsuba = gfmul_constraint_count::<Fr>(GfmulStrategy::Karatsuba);
+    // 128·128 and 3·64·64 + 128 bit products, plus 128 output parities
+    assert!(bit_serial > 128 * 128 && bit_serial < 128 * 128 + 128 * 12, "bit-serial {bit_serial}");
+    assert!(karatsuba > 3 * 64 * 64 + 128 && karatsuba < bit_serial, "karatsuba {karatsuba}");
+}
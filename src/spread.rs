@@ -0,0 +1,115 @@
+//! Carryless multiplication by bit-spread integer multiplication.
+//!
+//! [`crate::_sadmode_gfmul`] fails because integer multiplication carries between bit positions.
+//! Spreading bit `i` of each operand to bit `i·w` leaves a w-bit slot per power of x: the integer
+//! product then holds, in slot k, the column sum #{(i, j) : i + j = k, a_i = b_j = 1}, and the
+//! parity of each slot is bit k of the carryless product.
+//!
+//! No overflow, for 128-bit operands and w = [`SLOT_BITS`] = 8: a column k has one term per pair
+//! (i, k - i) with both indices in 0..128, so at most 128 terms. Hence every column sum is at most
+//! 128 < 2^8, no slot carries into the next, and each slot holds its column sum exactly. The bound
+//! is tight at k = 127 for a = b = 2^128 - 1, so w = 7 is unsound; [`min_slot_bits`] computes the
+//! bound in general and is checked against [`SLOT_BITS`] at compile time.
+//!
+//! [`spread_gfmul_field`] does the same in prime-field limbs, as a circuit would.
+
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+
+use crate::{parse_array_as_uint, parse_u128_as_array, reduce_product};
+
+#[cfg(test)] mod tests;
+
+/// Bits per slot: one slot per power of x.
+pub const SLOT_BITS: usize = 8;
+
+/// The smallest slot width w such that 2^w exceeds every column sum of an n×n-bit product, i.e.
+/// 2^w > n.
+pub const fn min_slot_bits(n: usize) -> usize { (usize::BITS - n.leading_zeros()) as usize }
+
+const _: () = assert!(SLOT_BITS >= min_slot_bits(128), "slots can carry for 128-bit operands");
+
+/// Spread bit `i` of `n` to bit `i·slot_bits`.
+pub fn spread(n: u128, slot_bits: usize) -> BigUint {
+    (0..128).filter(|i| (n >> i) & 1 == 1).fold(BigUint::default(), |mut acc, i| {
+        acc.set_bit((i * slot_bits) as u64, true);
+        acc
+    })
+}
+
+/// The parity of each slot of `n`, packed back into one bit per slot.
+fn slot_parities(n: &BigUint, slot_bits: usize, slots: usize) -> Vec<bool> {
+    (0..slots).map(|k| n.bit((k * slot_bits) as u64)).collect()
+}
+
+/// Carryless 128×128→256 multiplication as one integer multiplication of spread operands,
+/// returning (upper128, lower128). Only correct if `slot_bits` >= [`min_slot_bits`]`(128)`.
+pub fn clmul_spread(a: u128, b: u128, slot_bits: usize) -> (u128, u128) {
+    let product = spread(a, slot_bits) * spread(b, slot_bits);
+    let bits = slot_parities(&product, slot_bits, 256);
+    let pack = |bits: &[bool]| bits.iter().rev().fold(0u128, |acc, b| (acc << 1) | *b as u128);
+    (pack(&bits[128..]), pack(&bits[..128]))
+}
+
+/// Multiplication over the finite field $\text{GF}(2^{128})$ with a bit-spread integer multiply.
+pub fn spread_gfmul(a: [u8; 16], b: [u8; 16]) -> [u8; 16] {
+    let (upper, lower) = clmul_spread(parse_array_as_uint(a), parse_array_as_uint(b), SLOT_BITS);
+    parse_u128_as_array(reduce_product(upper, lower))
+}
+
+/// Slots per limb in the prime field F: the product of two limbs spans 2L - 1 slots, which must
+/// stay below the modulus, so (2L - 1)·[`SLOT_BITS`] < bits(p).
+///
+/// e.g. BN254's scalar field has a 254-bit modulus, so L = 16 and a 128-bit operand is 8 limbs.
+/// A field of at most [`SLOT_BITS`] bits cannot hold even one slot, and gets 0.
+pub fn limb_slots<F: PrimeField>() -> usize {
+    ((F::MODULUS_BIT_SIZE as usize - 1) / SLOT_BITS).div_ceil(2)
+}
+
+/// [`spread_gfmul`] with the spread operands split into limbs of [`limb_slots`] slots in the
+/// prime field F, as a circuit would compute it.
+///
+/// The limb products a_p·b_q with p + q = r are summed in F into a partial product at limb offset
+/// r. Each of its slots holds a partial column sum, itself at most the full column sum, so neither
+/// slots nor the field element overflow. Partial products at neighbouring offsets overlap by L - 1
+/// slots, and their parities are xored together.
+///
+/// # Panics
+/// If F has at most [`SLOT_BITS`] bits, so that [`limb_slots`] is 0.
+pub fn spread_gfmul_field<F: PrimeField>(a: [u8; 16], b: [u8; 16]) -> [u8; 16] {
+    let l = limb_slots::<F>();
+    assert!(
+        l >= 1,
+        "a {}-bit field cannot hold a {SLOT_BITS}-bit slot; spread_gfmul_field needs at least {} \
+         bits",
+        F::MODULUS_BIT_SIZE,
+        SLOT_BITS + 1
+    );
+    let limbs = |n: u128| -> Vec<F> {
+        (0..128)
+            .step_by(l)
+            .map(|start| {
+                let chunk = (n >> start) & (u128::MAX >> (128 - l.min(128 - start)));
+                F::from(spread(chunk, SLOT_BITS))
+            })
+            .collect()
+    };
+    let (a_limbs, b_limbs) = (limbs(parse_array_as_uint(a)), limbs(parse_array_as_uint(b)));
+
+    let mut bits = [false; 256];
+    for r in 0..a_limbs.len() + b_limbs.len() - 1 {
+        let partial: F = (0..a_limbs.len())
+            .filter(|p| r >= *p && r - p < b_limbs.len())
+            .map(|p| a_limbs[p] * b_limbs[r - p])
+            .sum();
+        let partial: BigUint = partial.into();
+        for (k, bit) in slot_parities(&partial, SLOT_BITS, 2 * l - 1).into_iter().enumerate() {
+            if r * l + k < 256 {
+                bits[r * l + k] ^= bit;
+            }
+        }
+    }
+
+    let pack = |bits: &[bool]| bits.iter().rev().fold(0u128, |acc, b| (acc << 1) | *b as u128);
+    parse_u128_as_array(reduce_product(pack(&bits[128..]), pack(&bits[..128])))
+}
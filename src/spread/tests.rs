@@ -0,0 +1,107 @@
+use ark_bn254::Fr;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+use crate::{gfmul, karatsuba::clmul128, tests::*};
+
+#[test]
+fn test_min_slot_bits() {
+    assert_eq!(min_slot_bits(1), 1);
+    assert_eq!(min_slot_bits(127), 7);
+    assert_eq!(min_slot_bits(128), 8);
+    assert_eq!(min_slot_bits(255), 8);
+    assert_eq!(min_slot_bits(256), 9);
+}
+
+#[test]
+fn test_spread() {
+    assert_eq!(spread(0, 8), BigUint::default());
+    assert_eq!(spread(1, 8), BigUint::from(1u8));
+    assert_eq!(spread(0b101, 8), BigUint::from(0x01_00_01u32));
+    assert_eq!(spread(1 << 127, 8), BigUint::from(1u8) << (127 * 8));
+}
+
+#[test]
+fn test_worst_case_column() {
+    // a = b = 2^128 - 1: column 127 sums 128 terms, the maximum
+    let product = spread(u128::MAX, SLOT_BITS) * spread(u128::MAX, SLOT_BITS);
+    let slot = |k: usize| (&product >> (k * SLOT_BITS)) & BigUint::from(0xffu8);
+    assert_eq!(slot(127), BigUint::from(128u8));
+    assert_eq!(slot(0), BigUint::from(1u8));
+    assert_eq!(slot(254), BigUint::from(1u8));
+
+    assert_eq!(clmul_spread(u128::MAX, u128::MAX, SLOT_BITS), clmul128(u128::MAX, u128::MAX));
+    // one bit narrower and column 127 carries into column 128
+    assert_ne!(clmul_spread(u128::MAX, u128::MAX, 7), clmul128(u128::MAX, u128::MAX));
+}
+
+#[test]
+fn test_clmul_spread_random() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..200 {
+        let (a, b): (u128, u128) = (rng.gen(), rng.gen());
+        assert_eq!(clmul_spread(a, b, SLOT_BITS), clmul128(a, b));
+        assert_eq!(clmul_spread(a, b, 11), clmul128(a, b));
+    }
+}
+
+#[test]
+fn test_spread_gfmul() {
+    for (a, b) in [(LONE, LONE), (RONE, RONE), (LC, RTHREE), (LC, RTHREE0), (H, X_1), (X_1, X_2)] {
+        assert_eq!(spread_gfmul(a, b), gfmul(a, b));
+        assert_eq!(spread_gfmul_field::<Fr>(a, b), gfmul(a, b));
+    }
+    assert_eq!(spread_gfmul_field::<Fr>([0xff; 16], [0xff; 16]), gfmul([0xff; 16], [0xff; 16]));
+}
+
+#[test]
+fn test_limb_slots() {
+    assert_eq!(Fr::MODULUS_BIT_SIZE, 254);
+    assert_eq!(limb_slots::<Fr>(), 16);
+    // the widest limb product stays below the modulus
+    let widest = spread(u16::MAX as u128, SLOT_BITS);
+    assert!(((&widest * &widest).bits() as u32) < Fr::MODULUS_BIT_SIZE);
+}
+
+// ark-ff 0.4's derive puts its impls in a const block
+#[allow(non_local_definitions)]
+mod small {
+    use ark_ff::{Fp64, MontBackend, MontConfig};
+
+    #[derive(MontConfig)]
+    #[modulus = "251"]
+    #[generator = "6"]
+    pub struct F251Config;
+    /// 8 bits, one too few for a slot
+    pub type F251 = Fp64<MontBackend<F251Config, 1>>;
+
+    #[derive(MontConfig)]
+    #[modulus = "257"]
+    #[generator = "3"]
+    pub struct F257Config;
+    /// 9 bits, the smallest field that works: one slot per limb
+    pub type F257 = Fp64<MontBackend<F257Config, 1>>;
+}
+use small::{F251, F257};
+
+#[test]
+fn test_small_fields() {
+    assert_eq!(limb_slots::<F251>(), 0);
+    assert_eq!(limb_slots::<F257>(), 1);
+    for (a, b) in [(H, X_1), (LC, RTHREE), ([0xff; 16], [0xff; 16])] {
+        assert_eq!(spread_gfmul_field::<F257>(a, b), gfmul(a, b));
+    }
+}
+
+#[test]
+#[should_panic(expected = "a 8-bit field cannot hold a 8-bit slot")]
+fn test_field_too_small() { spread_gfmul_field::<F251>(H, X_1); }
+
+#[test]
+fn test_spread_gfmul_field_random() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..200 {
+        let (a, b): ([u8; 16], [u8; 16]) = (rng.gen(), rng.gen());
+        assert_eq!(spread_gfmul_field::<Fr>(a, b), gfmul(a, b));
+    }
+}
@@ -0,0 +1,112 @@
+//! Shoup-style table multiplication by a fixed hash key.
+//!
+//! GHASH multiplies every block by the same key H, so [`GhashKey`] precomputes v·H for every
+//! 4-bit (16 entries) or 8-bit (256 entries) polynomial v once. A product X·H is then evaluated by
+//! Horner's rule over the nibbles or bytes of X, from the top:
+//!
+//! Z = Z·x^w + TABLE[chunk]
+//!
+//! where the w bits shifted out of Z by ·x^w represent a multiple of x^128, and are folded back
+//! in with a lookup into a small reduction table.
+
+use crate::{parse_array_as_uint, parse_u128_as_array};
+
+#[cfg(test)] mod tests;
+
+/// Reduction of the 4 bits shifted past x^127: `REDUCE_4[t]` = t·x^128 (mod the GHASH polynomial).
+pub(crate) const REDUCE_4: [u128; 16] = reduction_table();
+/// Reduction of the 8 bits shifted past x^127: `REDUCE_8[t]` = t·x^128 (mod the GHASH polynomial).
+pub(crate) const REDUCE_8: [u128; 256] = reduction_table();
+
+/// t·x^128 = t·(x^7 + x^2 + x + 1) for every t below N; at most 15 bits, so never spills again.
+const fn reduction_table<const N: usize>() -> [u128; N] {
+    let mut table = [0; N];
+    let mut t = 0;
+    while t < N {
+        let n = t as u128;
+        table[t] = n ^ (n << 1) ^ (n << 2) ^ (n << 7);
+        t += 1;
+    }
+    table
+}
+
+/// The memory/speed tradeoff of a [`GhashKey`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TableSize {
+    /// 16 entries (256 bytes); 32 lookups per multiplication.
+    Four,
+    /// 256 entries (4 KiB); 16 lookups per multiplication.
+    Eight,
+}
+
+impl TableSize {
+    /// Bits of the multiplicand consumed per lookup.
+    pub const fn bits(self) -> usize {
+        match self {
+            TableSize::Four => 4,
+            TableSize::Eight => 8,
+        }
+    }
+}
+
+/// A hash key H with precomputed multiples, for repeated multiplication by H.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GhashKey {
+    size:  TableSize,
+    /// `table[v]` = v·H, where bit `i` of `v` is the coefficient of $x^i$.
+    table: Vec<u128>,
+}
+
+impl GhashKey {
+    /// Precompute the multiples of the GHASH-convention hash key `h`.
+    pub fn new(h: [u8; 16], size: TableSize) -> Self {
+        // x^j·H for each bit j of the table index
+        let powers: Vec<u128> = (0..size.bits())
+            .scan(parse_array_as_uint(h), |h_xj, _| {
+                let out = *h_xj;
+                *h_xj = (*h_xj << 1) ^ REDUCE_4[(*h_xj >> 127) as usize];
+                Some(out)
+            })
+            .collect();
+
+        let table = (0..1usize << size.bits())
+            .map(|v| {
+                (0..size.bits()).filter(|j| v & (1 << j) != 0).fold(0, |acc, j| acc ^ powers[j])
+            })
+            .collect();
+
+        Self { size, table }
+    }
+
+    /// The table size chosen at construction.
+    pub fn size(&self) -> TableSize { self.size }
+
+    /// `table()[v]` = v·H, where bit `i` of `v` is the coefficient of $x^i$.
+    pub(crate) fn table(&self) -> &[u128] { &self.table }
+
+    /// Multiply the GHASH-convention field element `x` by H.
+    pub fn mul(&self, x: [u8; 16]) -> [u8; 16] {
+        parse_u128_as_array(self.mul_uint(parse_array_as_uint(x)))
+    }
+
+    /// [`crate::ghash`] with H's precomputed table.
+    pub fn ghash(&self, blocks: &[[u8; 16]]) -> [u8; 16] {
+        let x = blocks.iter().fold(0, |x, block| self.mul_uint(x ^ parse_array_as_uint(*block)));
+        parse_u128_as_array(x)
+    }
+
+    /// Horner's rule over the chunks of `x`, from the most significant.
+    fn mul_uint(&self, x: u128) -> u128 {
+        let w = self.size.bits();
+        let mask = (1 << w) - 1;
+        (0..128 / w).rev().fold(0, |z, k| {
+            let chunk = ((x >> (k * w)) & mask) as usize;
+            let spill = (z >> (128 - w)) as usize;
+            let reduce = match self.size {
+                TableSize::Four => REDUCE_4[spill],
+                TableSize::Eight => REDUCE_8[spill],
+            };
+            (z << w) ^ reduce ^ self.table[chunk]
+        })
+    }
+}
@@ -0,0 +1,69 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::*;
+use crate::{gfmul, ghash, tests::*};
+
+const SIZES: [TableSize; 2] = [TableSize::Four, TableSize::Eight];
+
+#[test]
+fn test_reduction_table() {
+    assert_eq!(REDUCE_4[0], 0);
+    assert_eq!(REDUCE_4[1], 135);
+    assert_eq!(REDUCE_4[2], 270);
+    assert_eq!(REDUCE_8[3], 135 ^ 270);
+    for t in 0..16 {
+        assert_eq!(REDUCE_4[t], REDUCE_8[t]);
+        assert_eq!(REDUCE_8[t], crate::galois_reduce(t as u128));
+    }
+}
+
+#[test]
+fn test_table_entries() {
+    for size in SIZES {
+        let key = GhashKey::new(H, size);
+        assert_eq!(key.size(), size);
+        assert_eq!(key.table.len(), 1 << size.bits());
+        assert_eq!(key.table[0], 0);
+        assert_eq!(key.table[1], parse_array_as_uint(H));
+        for v in 0..key.table.len() {
+            assert_eq!(parse_u128_as_array(key.table[v]), gfmul(parse_u128_as_array(v as u128), H));
+        }
+    }
+}
+
+#[test]
+fn test_mul_matches_gfmul() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for size in SIZES {
+        for h in [LONE, LTWO, LC, RONE, RTWO, RTHREE, RTHREE0, H] {
+            let key = GhashKey::new(h, size);
+            for x in [LONE, LTWO, LTHREE, L0C, RONE, RTHREE, RTHREE0, X_1, X_2, [0xff; 16]] {
+                assert_eq!(key.mul(x), gfmul(x, h), "{size:?}");
+            }
+        }
+        for _ in 0..200 {
+            let (x, h): ([u8; 16], [u8; 16]) = (rng.gen(), rng.gen());
+            assert_eq!(GhashKey::new(h, size).mul(x), gfmul(x, h), "{size:?}");
+        }
+    }
+}
+
+#[test]
+fn test_ghash_matches() {
+    let cases: [(_, &[[u8; 16]]); 9] = [
+        (LONE, &[LONE]),
+        (RONE, &[RONE]),
+        (LTWO, &[RONE]),
+        (LC, &[RTHREE]),
+        (LC, &[RTHREE0]),
+        (H, &[X_1]),
+        (H, &[X_1, X_2]),
+        (H, &[RONE, RTWO]),
+        (H, &[]),
+    ];
+    for size in SIZES {
+        for (h, blocks) in cases {
+            assert_eq!(GhashKey::new(h, size).ghash(blocks), ghash(h, blocks), "{size:?}");
+        }
+    }
+}
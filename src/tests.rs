@@ -9,27 +9,27 @@ use hex_literal::hex;
 use super::*;
 
 // LSBs
-const LONE: [u8; 16] = hex!("80000000000000000000000000000000"); // x^0
-const LTWO: [u8; 16] = hex!("40000000000000000000000000000000"); // x^1
-const LTHREE: [u8; 16] = hex!("c0000000000000000000000000000000"); // x^0+x^1
-const LFOUR: [u8; 16] = hex!("20000000000000000000000000000000"); // x^2
-const LEIGHT: [u8; 16] = hex!("10000000000000000000000000000000"); // x^3
-const LC: [u8; 16] = hex!("30000000000000000000000000000000"); // x^2+x^3
-const L0ONE: [u8; 16] = hex!("08000000000000000000000000000000"); // x^4
-const L0C: [u8; 16] = hex!("0c000000000000000000000000000000"); // x^4+x^5
+pub(crate) const LONE: [u8; 16] = hex!("80000000000000000000000000000000"); // x^0
+pub(crate) const LTWO: [u8; 16] = hex!("40000000000000000000000000000000"); // x^1
+pub(crate) const LTHREE: [u8; 16] = hex!("c0000000000000000000000000000000"); // x^0+x^1
+pub(crate) const LFOUR: [u8; 16] = hex!("20000000000000000000000000000000"); // x^2
+pub(crate) const LEIGHT: [u8; 16] = hex!("10000000000000000000000000000000"); // x^3
+pub(crate) const LC: [u8; 16] = hex!("30000000000000000000000000000000"); // x^2+x^3
+pub(crate) const L0ONE: [u8; 16] = hex!("08000000000000000000000000000000"); // x^4
+pub(crate) const L0C: [u8; 16] = hex!("0c000000000000000000000000000000"); // x^4+x^5
 
 // MSBs
-const RONE: [u8; 16] = hex!("00000000000000000000000000000001"); // x^127
-const RTWO: [u8; 16] = hex!("00000000000000000000000000000002"); // x^126
-const RTHREE: [u8; 16] = hex!("00000000000000000000000000000003"); // x^126+x^127
-const RTHREE0: [u8; 16] = hex!("00000000000000000000000000000030"); // x^122+x^123
+pub(crate) const RONE: [u8; 16] = hex!("00000000000000000000000000000001"); // x^127
+pub(crate) const RTWO: [u8; 16] = hex!("00000000000000000000000000000002"); // x^126
+pub(crate) const RTHREE: [u8; 16] = hex!("00000000000000000000000000000003"); // x^126+x^127
+pub(crate) const RTHREE0: [u8; 16] = hex!("00000000000000000000000000000030"); // x^122+x^123
 
 // const POLY: [u8; 16] = hex!("e1000000000000000000000000000000"); // 135
 
 // https://github.com/RustCrypto/universal-hashes/blob/master/ghash/tests/lib.rs//
-const H: [u8; 16] = hex!("25629347589242761d31f826ba4b757b");
-const X_1: [u8; 16] = hex!("4f4f95668c83dfb6401762bb2d01a262");
-const X_2: [u8; 16] = hex!("d1a24ddd2721d006bbe45f20d3c9f362");
+pub(crate) const H: [u8; 16] = hex!("25629347589242761d31f826ba4b757b");
+pub(crate) const X_1: [u8; 16] = hex!("4f4f95668c83dfb6401762bb2d01a262");
+pub(crate) const X_2: [u8; 16] = hex!("d1a24ddd2721d006bbe45f20d3c9f362");
 
 #[test]
 fn test_reverse_byte() {
@@ -122,6 +122,17 @@ fn test_galois_reduce() {
     );
 }
 
+#[test]
+fn test_reduce_product() {
+    for n in [0, 1, 2, 3, 4, 5, 1u128 << 120, 1u128 << 121, (1u128 << 121) + (1u128 << 120)] {
+        assert_eq!(reduce_product(n, 0), galois_reduce(n));
+    }
+    assert_eq!(reduce_product(u128::MAX, 0), galois_reduce(u128::MAX));
+    assert_eq!(reduce_product(0, u128::MAX), u128::MAX);
+    let (a, b) = (parse_array_as_uint(X_1), parse_array_as_uint(X_2));
+    assert_eq!(reduce_product(a, b), b ^ galois_reduce(a));
+}
+
 #[test]
 fn test_galois_product() {
     let mut v = vec![0; 128];
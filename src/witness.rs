@@ -0,0 +1,159 @@
+//! Named traces of every intermediate value in [`crate::gfmul`] and [`crate::ghash`].
+//!
+//! When a circuit port of gfmul disagrees with the Rust implementation, the trace lets us diff the
+//! two signal by signal. Names are stable and follow the steps of [`crate::gfmul`]:
+//!
+//! ```text
+//! a, b                   ; the operands
+//! b_bits[i]              ; bit decomposition of b, as from `parse_array_as_bits`
+//! product[i].upper       ; bits 128..256 of a * x^i, or 0 if b_bits[i] is unset
+//! product[i].lower       ; bits 0..128 of a * x^i, or 0 if b_bits[i] is unset
+//! upper128, lower128     ; xor of the partial products, before reduction
+//! reduction              ; galois_reduce(upper128)
+//! out                    ; lower128 ^ reduction
+//! ```
+//!
+//! and for [`ghash_with_witness`], over blocks j:
+//!
+//! ```text
+//! h, block[j]            ; the hash key and message blocks
+//! gfmul[j].<name>        ; the gfmul trace of (x[j-1] ^ block[j]) * h
+//! x[j]                   ; the running hash after block j
+//! out                    ; the final hash
+//! ```
+//!
+//! Field elements are recorded as integers with bit `i` the coefficient of $x^i$, as from
+//! `parse_array_as_uint`.
+
+use core::fmt;
+use std::collections::HashMap;
+
+use crate::{
+    galois_reduce, parse_array_as_bits, parse_array_as_uint, parse_u128_as_array, partial_products,
+};
+
+#[cfg(test)] mod tests;
+
+/// A single recorded signal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WitnessValue {
+    /// A single bit.
+    Bit(bool),
+    /// A 128-bit value with bit `i` the coefficient of $x^i$.
+    Field(u128),
+}
+
+impl fmt::Display for WitnessValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WitnessValue::Bit(b) => write!(f, "{}", *b as u8),
+            WitnessValue::Field(n) => write!(f, "{n:#034x}"),
+        }
+    }
+}
+
+/// An ordered list of named intermediate values.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Witness {
+    signals: Vec<(String, WitnessValue)>,
+    /// position in `signals` of the first signal with each name
+    index:   HashMap<String, usize>,
+}
+
+impl Witness {
+    /// The value recorded under `name`, if any.
+    pub fn get(&self, name: &str) -> Option<WitnessValue> {
+        self.index.get(name).map(|i| self.signals[*i].1)
+    }
+
+    /// Every signal, in the order it was computed.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, WitnessValue)> {
+        self.signals.iter().map(|(n, v)| (n.as_str(), *v))
+    }
+
+    /// The number of signals recorded.
+    pub fn len(&self) -> usize { self.signals.len() }
+
+    /// Whether no signals are recorded.
+    pub fn is_empty(&self) -> bool { self.signals.is_empty() }
+
+    /// The names of signals in `self` that are missing from, or differ in, `other`.
+    pub fn diff<'a>(&'a self, other: &Witness) -> Vec<&'a str> {
+        self.iter().filter(|(name, v)| other.get(name) != Some(*v)).map(|(name, _)| name).collect()
+    }
+
+    fn push(&mut self, name: impl Into<String>, value: WitnessValue) {
+        let name = name.into();
+        self.index.entry(name.clone()).or_insert(self.signals.len());
+        self.signals.push((name, value));
+    }
+
+    fn push_field(&mut self, name: impl Into<String>, n: u128) {
+        self.push(name, WitnessValue::Field(n));
+    }
+
+    /// Append every signal of `other` under `prefix.`.
+    fn extend_prefixed(&mut self, prefix: &str, other: Witness) {
+        for (name, value) in other.signals {
+            self.push(format!("{prefix}.{name}"), value);
+        }
+    }
+}
+
+impl fmt::Display for Witness {
+    /// One `name = value` line per signal.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.iter().try_for_each(|(name, value)| writeln!(f, "{name} = {value}"))
+    }
+}
+
+/// [`crate::gfmul`], recording every intermediate.
+pub fn gfmul_with_witness(a: [u8; 16], b: [u8; 16]) -> ([u8; 16], Witness) {
+    let mut w = Witness::default();
+    let a_uint = parse_array_as_uint(a);
+    let b_arr = parse_array_as_bits(b);
+    w.push_field("a", a_uint);
+    w.push_field("b", parse_array_as_uint(b));
+    b_arr
+        .iter()
+        .enumerate()
+        .for_each(|(i, bit)| w.push(format!("b_bits[{i}]"), WitnessValue::Bit(*bit)));
+
+    let (mut upper128, mut lower128) = (0, 0);
+    for (i, (upper, lower)) in partial_products(a_uint, b_arr).into_iter().enumerate() {
+        w.push_field(format!("product[{i}].upper"), upper);
+        w.push_field(format!("product[{i}].lower"), lower);
+        upper128 ^= upper;
+        lower128 ^= lower;
+    }
+    w.push_field("upper128", upper128);
+    w.push_field("lower128", lower128);
+
+    let reduction = galois_reduce(upper128);
+    w.push_field("reduction", reduction);
+    w.push_field("out", lower128 ^ reduction);
+
+    (parse_u128_as_array(lower128 ^ reduction), w)
+}
+
+/// [`crate::ghash`], recording the trace of every gfmul and the running hash after every block.
+pub fn ghash_with_witness(hashkey: [u8; 16], blocks: &[[u8; 16]]) -> ([u8; 16], Witness) {
+    let mut w = Witness::default();
+    w.push_field("h", parse_array_as_uint(hashkey));
+    blocks
+        .iter()
+        .enumerate()
+        .for_each(|(j, block)| w.push_field(format!("block[{j}]"), parse_array_as_uint(*block)));
+
+    let mut x = [0u8; 16];
+    for (j, block) in blocks.iter().enumerate() {
+        (0..16).for_each(|i| x[i] ^= block[i]);
+        let (product, gfmul_witness) = gfmul_with_witness(x, hashkey);
+        w.extend_prefixed(&format!("gfmul[{j}]"), gfmul_witness);
+        x = product;
+        w.push_field(format!("x[{j}]"), parse_array_as_uint(x));
+    }
+    w.push_field("out", parse_array_as_uint(x));
+
+    (x, w)
+}
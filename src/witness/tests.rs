@@ -0,0 +1,72 @@
+use super::*;
+use crate::{gfmul, ghash, tests::*};
+
+#[test]
+fn test_gfmul_witness_result() {
+    for (a, b) in [(LONE, LONE), (RONE, RONE), (LC, RTHREE), (LC, RTHREE0), (H, X_1), (X_1, X_2)] {
+        let (out, w) = gfmul_with_witness(a, b);
+        assert_eq!(out, gfmul(a, b));
+        assert_eq!(w.get("out"), Some(WitnessValue::Field(parse_array_as_uint(out))));
+    }
+}
+
+#[test]
+fn test_gfmul_witness_signals() {
+    let (_, w) = gfmul_with_witness(LC, RTHREE);
+    // a, b, 128 bits, 256 partial product halves, upper128, lower128, reduction, out
+    assert_eq!(w.len(), 2 + 128 + 256 + 4);
+    assert_eq!(w.get("a"), Some(WitnessValue::Field(12)));
+    assert_eq!(w.get("b_bits[126]"), Some(WitnessValue::Bit(true)));
+    assert_eq!(w.get("b_bits[0]"), Some(WitnessValue::Bit(false)));
+    assert_eq!(w.get("product[0].lower"), Some(WitnessValue::Field(0)));
+    // (x^2 + x^3) * x^127 = x^129 + x^130
+    assert_eq!(w.get("product[127].upper"), Some(WitnessValue::Field(0b110)));
+    assert_eq!(w.get("product[127].lower"), Some(WitnessValue::Field(0)));
+    // (x^2 + x^3)(x^126 + x^127) = x^128 + x^130
+    assert_eq!(w.get("upper128"), Some(WitnessValue::Field(0b101)));
+    assert_eq!(w.get("lower128"), Some(WitnessValue::Field(0)));
+    assert_eq!(w.get("reduction"), Some(WitnessValue::Field(galois_reduce(0b101))));
+    assert_eq!(w.get("nonexistent"), None);
+}
+
+#[test]
+fn test_ghash_witness() {
+    let (out, w) = ghash_with_witness(H, &[X_1, X_2]);
+    assert_eq!(out, ghash(H, &[X_1, X_2]));
+    assert_eq!(w.get("x[0]"), Some(WitnessValue::Field(parse_array_as_uint(ghash(H, &[X_1])))));
+    assert_eq!(w.get("x[1]"), Some(WitnessValue::Field(parse_array_as_uint(out))));
+    assert_eq!(w.get("out"), w.get("x[1]"));
+    assert_eq!(w.get("gfmul[0].a"), Some(WitnessValue::Field(parse_array_as_uint(X_1))));
+    assert_eq!(w.get("gfmul[1].out"), w.get("x[1]"));
+    assert_eq!(w.get("gfmul[0].b"), w.get("h"));
+
+    let (out, w) = ghash_with_witness(H, &[]);
+    assert_eq!(out, [0; 16]);
+    assert_eq!(w.len(), 2);
+}
+
+#[test]
+fn test_diff_and_display() {
+    let (_, w1) = gfmul_with_witness(H, X_1);
+    let (_, w2) = gfmul_with_witness(H, X_1);
+    assert!(w1.diff(&w2).is_empty());
+
+    // a different b changes b, its bits, its partial products and everything downstream, never a
+    let (_, w3) = gfmul_with_witness(H, X_2);
+    let diff = w1.diff(&w3);
+    assert!(diff.contains(&"b") && diff.contains(&"out") && !diff.contains(&"a"));
+
+    // traces of long messages diff in linear time
+    let blocks = [X_1; 64];
+    let (_, long) = ghash_with_witness(H, &blocks);
+    let (_, other) = ghash_with_witness(H, &[&blocks[..63], &[X_2]].concat());
+    assert!(long.len() > 20_000);
+    assert_eq!(long.diff(&long), Vec::<&str>::new());
+    let diff = long.diff(&other);
+    assert!(diff.contains(&"block[63]") && !diff.iter().any(|name| name.starts_with("x[62]")));
+
+    let display = w1.to_string();
+    assert_eq!(display.lines().count(), w1.len());
+    assert!(display.starts_with(&format!("a = {:#034x}\n", parse_array_as_uint(H))));
+    assert!(display.contains("b_bits[0] = 0\n"));
+}
@@ -0,0 +1,207 @@
+//! Wycheproof AES-GCM and GMAC vectors: a loader for the `aead_test_schema.json` and
+//! `mac_with_iv_test_schema.json` files and a runner that reports, flag by flag, whether
+//! [`AesGcm`] computes tags as expected and whether [`AesGcm::open`] accepts or rejects them.
+//!
+//! Each test expects a `result`: `valid` inputs must be accepted, `invalid` ones rejected, and
+//! `acceptable` ones, legal but unusual inputs such as short IVs, may go either way. Its `flags`
+//! name the edge case it exercises. Upstream files are not bundled: the tests run them from
+//! `WYCHEPROOF_DIR` when it is set. `vectors/local` holds local fixtures in the same schema, which
+//! are not upstream data.
+
+use std::{collections::BTreeMap, fmt};
+
+use anyhow::{bail, ensure, Context, Result};
+use serde_json::Value;
+
+use crate::AesGcm;
+
+#[cfg(test)] mod tests;
+
+/// The `result` a test expects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Expected {
+    Valid,
+    Invalid,
+    Acceptable,
+}
+
+/// One test. GMAC tests authenticate `msg` as associated data with an empty plaintext, and are
+/// loaded that way: their `msg` goes in `aad`, and `msg` and `ct` are empty.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WycheproofTest {
+    pub tc_id:    u64,
+    pub comment:  String,
+    pub flags:    Vec<String>,
+    pub key:      Vec<u8>,
+    pub iv:       Vec<u8>,
+    pub aad:      Vec<u8>,
+    pub msg:      Vec<u8>,
+    pub ct:       Vec<u8>,
+    pub tag:      Vec<u8>,
+    pub expected: Expected,
+}
+
+/// Load the tests of an AES-GCM (`AeadTest`) or GMAC (`MacWithIvTest`) file.
+pub fn load(json: &str) -> Result<Vec<WycheproofTest>> {
+    let root: Value = serde_json::from_str(json)?;
+    let groups = root["testGroups"].as_array().context("missing testGroups")?;
+
+    let mut tests = vec![];
+    for group in groups {
+        let gmac = match group["type"].as_str() {
+            Some("AeadTest") => false,
+            Some("MacWithIvTest") => true,
+            other => bail!("unsupported test group type {other:?}"),
+        };
+        for test in group["tests"].as_array().context("missing tests")? {
+            let tc_id = test["tcId"].as_u64().context("missing tcId")?;
+            let bytes = |field: &str| -> Result<Vec<u8>> {
+                let s = test[field]
+                    .as_str()
+                    .with_context(|| format!("tcId {tc_id}: missing {field}"))?;
+                hex::decode(s).with_context(|| format!("tcId {tc_id}: bad hex in {field}"))
+            };
+            let expected = match test["result"].as_str() {
+                Some("valid") => Expected::Valid,
+                Some("invalid") => Expected::Invalid,
+                Some("acceptable") => Expected::Acceptable,
+                other => bail!("tcId {tc_id}: unknown result {other:?}"),
+            };
+            let flags = test["flags"].as_array().map_or(vec![], |f| {
+                f.iter().filter_map(|f| f.as_str().map(String::from)).collect()
+            });
+
+            let (aad, msg, ct) = if gmac {
+                (bytes("msg")?, vec![], vec![])
+            } else {
+                (bytes("aad")?, bytes("msg")?, bytes("ct")?)
+            };
+            tests.push(WycheproofTest {
+                tc_id,
+                comment: test["comment"].as_str().unwrap_or_default().to_string(),
+                flags,
+                key: bytes("key")?,
+                iv: bytes("iv")?,
+                aad,
+                msg,
+                ct,
+                tag: bytes("tag")?,
+                expected,
+            });
+        }
+    }
+    Ok(tests)
+}
+
+/// The tag computation: sealing `msg` must give `ct` and `tag`. Only meaningful for tests whose
+/// tag is genuine, i.e. not `invalid` ones.
+pub fn computes(test: &WycheproofTest) -> Result<()> {
+    let gcm = AesGcm::new(&test.key)?;
+    let (ct, tag) = gcm.seal(&test.iv, &test.aad, &test.msg)?;
+    ensure!(ct == test.ct, "ciphertext mismatch");
+    ensure!(tag.get(..test.tag.len()) == Some(&test.tag[..]), "computed tag differs");
+    Ok(())
+}
+
+/// The verification path: [`AesGcm::open`] on `ct` and `tag`, returning the plaintext, or the
+/// reason it was rejected.
+pub fn verifies(test: &WycheproofTest) -> Result<Vec<u8>> {
+    AesGcm::new(&test.key)?.open(&test.iv, &test.aad, &test.ct, &test.tag)
+}
+
+/// The outcome of one test.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestResult {
+    pub tc_id:    u64,
+    pub flags:    Vec<String>,
+    pub expected: Expected,
+    /// why the tag computation or decryption went wrong; `None` if they were right, and for
+    /// `invalid` tests, which have no correct tag to compute
+    pub mismatch: Option<String>,
+    /// `None` if `open` accepted, else why it rejected
+    pub rejected: Option<String>,
+}
+
+impl TestResult {
+    /// Valid tests must compute the tag and be accepted by `open`, and invalid ones rejected by
+    /// `open`. Acceptable tests may be rejected, but if accepted must be right.
+    pub fn passed(&self) -> bool {
+        match self.expected {
+            Expected::Valid => self.mismatch.is_none() && self.rejected.is_none(),
+            Expected::Invalid => self.rejected.is_some(),
+            Expected::Acceptable => self.mismatch.is_none() || self.rejected.is_some(),
+        }
+    }
+}
+
+/// Passed and failed counts of the tests carrying one flag.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FlagSummary {
+    pub passed: usize,
+    pub failed: usize,
+}
+
+/// The results of a run.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WycheproofReport {
+    pub results: Vec<TestResult>,
+}
+
+impl WycheproofReport {
+    /// Run every test.
+    pub fn run(tests: &[WycheproofTest]) -> Self {
+        let results = tests
+            .iter()
+            .map(|t| {
+                let opened = verifies(t);
+                let mismatch = match (t.expected, &opened) {
+                    (Expected::Invalid, _) => None,
+                    (_, Ok(pt)) if *pt != t.msg => Some("decryption mismatch".to_string()),
+                    _ => computes(t).err().map(|e| format!("{e:#}")),
+                };
+                TestResult {
+                    tc_id: t.tc_id,
+                    flags: t.flags.clone(),
+                    expected: t.expected,
+                    mismatch,
+                    rejected: opened.err().map(|e| format!("{e:#}")),
+                }
+            })
+            .collect();
+        Self { results }
+    }
+
+    /// Results per flag; tests without flags are counted under `""`.
+    pub fn by_flag(&self) -> BTreeMap<&str, FlagSummary> {
+        let mut flags: BTreeMap<&str, FlagSummary> = BTreeMap::new();
+        for result in &self.results {
+            let names: Vec<&str> = match result.flags.is_empty() {
+                true => vec![""],
+                false => result.flags.iter().map(String::as_str).collect(),
+            };
+            for name in names {
+                let summary = flags.entry(name).or_default();
+                match result.passed() {
+                    true => summary.passed += 1,
+                    false => summary.failed += 1,
+                }
+            }
+        }
+        flags
+    }
+
+    /// The tests that did not get their expected result.
+    pub fn failures(&self) -> impl Iterator<Item = &TestResult> {
+        self.results.iter().filter(|r| !r.passed())
+    }
+}
+
+impl fmt::Display for WycheproofReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (flag, summary) in self.by_flag() {
+            let flag = if flag.is_empty() { "(no flags)" } else { flag };
+            writeln!(f, "{flag:<24} passed {:>4}  failed {:>4}", summary.passed, summary.failed)?;
+        }
+        Ok(())
+    }
+}
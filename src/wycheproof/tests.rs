@@ -0,0 +1,149 @@
+use std::path::PathBuf;
+
+use aes_gcm::{
+    aead::{
+        consts::{U12, U16, U8},
+        generic_array::ArrayLength,
+        AeadInPlace, KeyInit,
+    },
+    aes::Aes128,
+    AesGcm as RcAesGcm,
+};
+
+use super::*;
+
+/// a local fixture in `vectors/local`, in the Wycheproof schema but not upstream data
+fn fixture(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("vectors/local").join(name);
+    std::fs::read_to_string(path).unwrap()
+}
+
+/// an upstream file from `WYCHEPROOF_DIR`, the `testvectors_v1` directory of a Wycheproof
+/// checkout, or `None` when it is not set
+fn upstream(name: &str) -> Option<String> {
+    let dir = PathBuf::from(std::env::var_os("WYCHEPROOF_DIR")?);
+    Some(std::fs::read_to_string(dir.join(name)).unwrap())
+}
+
+/// seal with RustCrypto AES-128-GCM and a given nonce size
+fn rustcrypto_seal<N: ArrayLength<u8>>(test: &WycheproofTest) -> (Vec<u8>, Vec<u8>) {
+    let cipher = RcAesGcm::<Aes128, N>::new_from_slice(&test.key).unwrap();
+    let mut buf = test.msg.clone();
+    let tag = cipher.encrypt_in_place_detached(test.iv.as_slice().into(), &test.aad, &mut buf);
+    (buf, tag.unwrap().to_vec())
+}
+
+#[test]
+fn test_load() {
+    let tests = load(&fixture("aes_gcm_fixture.json")).unwrap();
+    assert!(!tests.is_empty());
+    let gmac = load(&fixture("gmac_fixture.json")).unwrap();
+    assert!(gmac.iter().all(|t| t.msg.is_empty() && t.ct.is_empty() && !t.aad.is_empty()));
+
+    assert!(load("{}").is_err());
+    assert!(load(r#"{"testGroups": [{"type": "MacTest", "tests": []}]}"#).is_err());
+    let bad_result = r#"{"testGroups": [{"type": "MacWithIvTest", "tests": [
+        {"tcId": 1, "key": "", "iv": "", "msg": "", "tag": "", "result": "maybe"}]}]}"#;
+    assert!(load(bad_result).is_err());
+}
+
+#[test]
+fn test_passed() {
+    let result = |expected, mismatch: Option<&str>, rejected: Option<&str>| TestResult {
+        tc_id: 0,
+        flags: vec![],
+        expected,
+        mismatch: mismatch.map(String::from),
+        rejected: rejected.map(String::from),
+    };
+    assert!(result(Expected::Valid, None, None).passed());
+    assert!(!result(Expected::Valid, Some("no"), None).passed());
+    assert!(!result(Expected::Valid, None, Some("no")).passed());
+    assert!(result(Expected::Invalid, None, Some("no")).passed());
+    assert!(!result(Expected::Invalid, None, None).passed());
+    assert!(result(Expected::Acceptable, None, None).passed());
+    assert!(result(Expected::Acceptable, Some("no"), Some("no")).passed());
+    assert!(!result(Expected::Acceptable, Some("no"), None).passed());
+}
+
+#[test]
+fn test_invalid_goes_through_open() {
+    let tests = load(&fixture("aes_gcm_fixture.json")).unwrap();
+    let valid = tests.iter().find(|t| t.expected == Expected::Valid).unwrap();
+
+    // a genuine tag marked invalid is accepted by open, so the test fails
+    let marked = WycheproofTest { expected: Expected::Invalid, ..valid.clone() };
+    let report = WycheproofReport::run(&[marked]);
+    assert_eq!(report.results[0].rejected, None);
+    assert_eq!(report.failures().count(), 1);
+
+    // a modified tag is rejected by open itself, not by comparing with the computed tag
+    let mut modified = WycheproofTest { expected: Expected::Invalid, ..valid.clone() };
+    modified.tag[0] ^= 1;
+    let result = &WycheproofReport::run(&[modified]).results[0];
+    assert_eq!(result.rejected.as_deref(), Some("GCM tag mismatch"));
+    assert_eq!(result.mismatch, None);
+}
+
+#[test]
+fn test_fixture_aes_gcm() {
+    let report = WycheproofReport::run(&load(&fixture("aes_gcm_fixture.json")).unwrap());
+    assert_eq!(report.failures().collect::<Vec<_>>(), Vec::<&TestResult>::new(), "\n{report}");
+
+    let flags = report.by_flag();
+    assert!(flags["ModifiedTag"].passed > 0 && flags["LongIv"].passed > 0);
+    for result in &report.results {
+        if result.flags.contains(&"ModifiedTag".into()) {
+            assert_eq!(
+                result.rejected.as_deref(),
+                Some("GCM tag mismatch"),
+                "tcId {}",
+                result.tc_id
+            );
+        }
+        // an empty IV is rejected before any tag is computed
+        if result.flags.contains(&"ZeroLengthIv".into()) {
+            assert!(result.rejected.as_ref().unwrap().contains("IV"), "tcId {}", result.tc_id);
+        }
+    }
+}
+
+#[test]
+fn test_fixture_gmac() {
+    let report = WycheproofReport::run(&load(&fixture("gmac_fixture.json")).unwrap());
+    assert_eq!(report.failures().count(), 0, "\n{report}");
+    assert!(report.by_flag().contains_key(""));
+    // short IVs are legal in GCM, and the crate takes them
+    let acceptable = report.results.iter().filter(|r| r.expected == Expected::Acceptable);
+    assert!(acceptable.clone().count() > 0 && acceptable.clone().all(|r| r.rejected.is_none()));
+}
+
+#[test]
+fn test_fixtures_match_rustcrypto() {
+    // the fixtures are local, so every entry that must be accepted is checked against an
+    // independent implementation rather than against the crate
+    let mut tests = load(&fixture("aes_gcm_fixture.json")).unwrap();
+    tests.extend(load(&fixture("gmac_fixture.json")).unwrap());
+    let mut checked = 0;
+    for test in tests.iter().filter(|t| t.expected != Expected::Invalid) {
+        let sealed = match test.iv.len() {
+            8 => rustcrypto_seal::<U8>(test),
+            12 => rustcrypto_seal::<U12>(test),
+            16 => rustcrypto_seal::<U16>(test),
+            n => panic!("tcId {}: no RustCrypto nonce size for a {n}-byte IV", test.tc_id),
+        };
+        assert_eq!(sealed, (test.ct.clone(), test.tag.clone()), "tcId {}", test.tc_id);
+        checked += 1;
+    }
+    assert_eq!(checked, 6);
+}
+
+#[test]
+fn test_wycheproof_upstream() {
+    // upstream files are not bundled; this runs them when WYCHEPROOF_DIR points at a checkout
+    for name in ["aes_gcm_test.json", "gmac_test.json"] {
+        let Some(json) = upstream(name) else { return };
+        let report = WycheproofReport::run(&load(&json).unwrap());
+        assert_eq!(report.failures().count(), 0, "{name}\n{report}");
+    }
+}